@@ -1,3 +1,4 @@
 pub mod random_player;
 pub mod classic_player;
+pub mod classic_player_plus;
 pub mod simple_nn;
\ No newline at end of file
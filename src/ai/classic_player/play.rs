@@ -1,14 +1,15 @@
 use std::sync::Arc;
 
-use rand::thread_rng;
 use rand::seq::SliceRandom;
 
 use crate::backend::round::action::Action;
+use crate::backend::round::cities_knights::{ProgressCard, BARBARIAN_DISTANCE};
 use crate::backend::round::cards::CardType::{KnightCard, MonopolyCard, PlentyCard, RoadsCard, VPCard};
 use crate::backend::setup::game::Game;
 
 use crate::backend::setup::game_parameters::GameParameters;
 use crate::backend::setup::node_status::NodeStatus::{Adjacent, Citied, Free, Settled};
+use crate::backend::setup::terrain::Terrain;
 
 use crate::backend::setup::player::PlayerType::Classic;
 
@@ -34,7 +35,7 @@ pub fn play(game: &Game, legal_actions: Vec<Action>) -> Option<Action> {
                                 .map(|i| legal_actions[*i].clone())
                                 .collect();
 
-    let mut rng = thread_rng();
+    let mut rng = game.get_seat_rng();
     let selected_action = best_actions.choose(&mut rng).cloned();
 
     // println!("Dice throws: {:#?}", game.log[game.log.len() - 1].count_dice_draws);
@@ -67,6 +68,14 @@ fn evaluate_actions(game: &Game, input_actions: &Vec<Action>) -> Vec<f64> {
                 evaluate_robber(i_robber, i_tile, i_victim, game)
             },
 
+            Action::Pirate(_, _, i_victim) => {
+                // the pirate does not block any production, only the stolen card counts
+                match i_victim {
+                    Some(_) => 1.0,
+                    None => 0.0,
+                }
+            },
+
             Action::DiscardCards(v_discard) => {
                 let v_resources = evaluate_resources(game);
                 
@@ -78,16 +87,30 @@ fn evaluate_actions(game: &Game, input_actions: &Vec<Action>) -> Vec<f64> {
                                         .map(|(&b,d)| b.checked_sub(*d).unwrap_or(0_u32))
                                         .collect();
 
+                // discarded commodities (Cities & Knights) follow the resources and are worth one resource each
+                let n_commodities_discarded = v_discard.iter().skip(budget.len()).sum::<u32>();
+                let mean_resource_value = v_resources.iter().sum::<f64>() / v_resources.len() as f64;
+
                 new_budget.iter()
                         .zip(v_resources.iter())
                         .map(|(b, v)| v * *b as f64)
                         .sum::<f64>()
+                        - n_commodities_discarded as f64 * mean_resource_value
             },
 
             Action::NoDiscard => {
                 0.0
             }
 
+            Action::ChooseGold(v_chosen) => {
+                let v_resources = evaluate_resources(game);
+
+                v_chosen.iter()
+                        .zip(v_resources.iter())
+                        .map(|(c, v)| v * *c as f64)
+                        .sum::<f64>()
+            },
+
             Action::NoCardPlay => {
                 0.0
             },
@@ -191,6 +214,16 @@ fn evaluate_actions(game: &Game, input_actions: &Vec<Action>) -> Vec<f64> {
                 evaluate_road(*start_node as usize, *end_node as usize, v_resources, game)
             },
 
+            Action::BuildShip(start_node, end_node) => {
+                // ships are valued like roads
+                let v_resources = &evaluate_resources(game);
+                evaluate_road(*start_node as usize, *end_node as usize, v_resources, game)
+            },
+
+            Action::MoveShip(_, _, _, _) => {
+                0.0
+            },
+
             Action::BuildSettlement(settlement_node) => {
                 let v_resources = &evaluate_resources(game);
                 evaluate_settlement(*settlement_node as usize, v_resources, game)
@@ -201,6 +234,32 @@ fn evaluate_actions(game: &Game, input_actions: &Vec<Action>) -> Vec<f64> {
                 evaluate_city(*city_node as usize, v_resources, game)
             },
 
+            Action::BuildKnight(_) | Action::PromoteKnight(_) => {
+                0.5
+            },
+
+            Action::ActivateKnight(_) => {
+                // only worth the grain while the barbarians are close
+                game.round.board.barbarian_position as f64 / BARBARIAN_DISTANCE as f64
+            },
+
+            Action::BuildImprovement(_) => {
+                1.0
+            },
+
+            Action::PlayProgressCard(card) => {
+                match card {
+                    // only worth it while the barbarians are close
+                    ProgressCard::Warlord => game.round.board.barbarian_position as f64 / BARBARIAN_DISTANCE as f64,
+                    _ => 1.0,
+                }
+            },
+
+            Action::CommodityTrade(_, r_demanded) => {
+                // the value of the resource received (commodities have no resource value)
+                evaluate_resources(game)[*r_demanded as usize]
+            },
+
             Action::BuyDevCard => {
                 // NOTE: so far, the ex ante value of a dev card only depends on the probability of drawing a victory point. Other cards would require forward-looking beliefs.
 
@@ -242,7 +301,10 @@ fn evaluate_resources(game: &Game) -> Vec<f64> {
     for i_resource in 0..game.parameters.n_resources {
         let player_id = game.round.active_player as usize;
 
-        let default_weights = vec![vec![0.0; 13]; 5];
+        let n_resources = game.parameters.n_resources as usize;
+
+        // one row of weights per resource: intercept, score, drawn resources and budget per resource
+        let default_weights = vec![vec![0.0; 3 + 2 * n_resources]; n_resources];
 
         let weights = match &game.parameters.v_players[player_id].player_type {
             Classic(w) => w,
@@ -251,32 +313,15 @@ fn evaluate_resources(game: &Game) -> Vec<f64> {
 
         let r_weights = &weights[i_resource as usize];
 
-        let covariates = vec![
+        let mut covariates = vec![
             1.0,
             //game.log[&game.log.len() - 1].log_id as f64,
             game.round.board.scores[player_id] as f64,
-            game.round.board.total_drawn_resources[player_id][0] as f64,
-            game.round.board.total_drawn_resources[player_id][1] as f64,
-            game.round.board.total_drawn_resources[player_id][2] as f64,
-            game.round.board.total_drawn_resources[player_id][3] as f64,
-            game.round.board.total_drawn_resources[player_id][4] as f64,
-            // 0.0,
-            // 0.0,
-            // 0.0,
-            // 0.0,
-            // 0.0, 
-            game.round.board.budgets[player_id][0] as f64,
-            game.round.board.budgets[player_id][1] as f64,
-            game.round.board.budgets[player_id][2] as f64,
-            game.round.board.budgets[player_id][3] as f64,
-            game.round.board.budgets[player_id][4] as f64,
-            // 0.0,
-            // 0.0,
-            // 0.0,
-            // 0.0,
-            // 0.0,
         ];
 
+        covariates.extend(game.round.board.total_drawn_resources[player_id].iter().map(|&r| r as f64));
+        covariates.extend(game.round.board.budgets[player_id].iter().map(|&b| b as f64));
+
         v_resources.push(r_weights.iter().zip(covariates.iter()).map(|(w, x)| w * x).sum());
     }
 
@@ -294,9 +339,11 @@ fn evaluate_tile(i_tile: usize, v_resources: &Vec<f64>, game: &Game) -> f64 {
         None => return 0.0,
     };
 
-    match tile.resource {
-        Some(r) => rng_prob * v_resources[r as usize],
-        None => 0.0,
+    // gold fields pay the most valuable resource
+    match Terrain::from_resource(tile.resource, game.parameters.n_resources) {
+        Terrain::Land(r) => rng_prob * v_resources[r as usize],
+        Terrain::Gold => rng_prob * v_resources.iter().cloned().fold(0.0, f64::max),
+        _ => 0.0,
     }
 }
 
@@ -326,7 +373,7 @@ fn evaluate_node(i_node: usize, v_resources: &Vec<f64>, game: &Game) -> f64 {
     value
 }
 
-fn get_rng_probability(s: usize, parameters: &GameParameters) -> f64 {
+pub fn get_rng_probability(s: usize, parameters: &GameParameters) -> f64 {
 
     let n = parameters.n_faces as usize;
     let d = parameters.n_dice as usize;
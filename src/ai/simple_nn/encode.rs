@@ -1,4 +1,6 @@
-use crate::backend::{round::action::Action, setup::game::Game, logging::log_entry::LogEntry};
+use crate::backend::{io::encode_log::LogEncoding, round::action::Action, setup::game::Game, logging::log_entry::LogEntry};
+
+use super::evaluate::INPUT_SIZE;
 
 
 pub fn encode_action(game: &Game, legal_action: &Action) -> Vec<u32> {
@@ -10,9 +12,14 @@ pub fn encode_action(game: &Game, legal_action: &Action) -> Vec<u32> {
 
 fn extend_log(game: &Game, legal_action: &Action) -> LogEntry {
 
-    let mut game_clone = game.clone();
+    let mut game_clone = game.clone_for_lookahead();
 
     game_clone.take_action(legal_action.clone(), None);
 
     game_clone.log[game_clone.log.len() - 1].clone()
-}
\ No newline at end of file
+}
+
+// the network was trained on the base layout of the default board. Other layouts and board sizes do not fit its input
+pub fn fits_network(game: &Game) -> bool {
+    game.get_log_encoding() == LogEncoding::Base && game.hot_encode_log(&game.log[0]).len() - 1 == INPUT_SIZE as usize
+}
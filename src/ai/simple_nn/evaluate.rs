@@ -1,6 +1,11 @@
+use std::{error::Error, path::PathBuf};
+
 use tch::{nn, nn::Module, nn::OptimizerConfig, Device, Tensor};
 
-use crate::{ai::simple_nn::encode::{self, encode_action}, backend::{round::action::Action, setup::game::Game}};
+use crate::{ai::simple_nn::encode::{self, encode_action}, backend::{io::assets::find_asset_file, round::action::Action, setup::game::Game}};
+
+// width of the base layout of the hot encoding on the default board, without the log id
+pub const INPUT_SIZE: i64 = 2368;
 
 #[derive(Debug)]
 pub struct SimpleNN {
@@ -140,7 +145,7 @@ impl SimpleNN {
 
 impl nn::Module for SimpleNN {
     fn forward(&self, xs: &Tensor) -> Tensor {
-        xs.view([-1, 2368])
+        xs.view([-1, INPUT_SIZE])
             .apply(&self.layer1).relu()
             .apply(&self.layer2).relu()
             .apply(&self.layer3).relu()
@@ -153,20 +158,33 @@ impl nn::Module for SimpleNN {
 }
 
 
-pub fn evaluate_actions(game: &Game, legal_actions: &Vec<Action>) -> Vec<f32> {
+// the model is not shipped with the game: $CATAN_ASSET_DIR/ai/simple_nn/ or data/ai/simple_nn/, else where the training script saves it
+const MODEL_ASSET: &str = "ai/simple_nn/weights_short.safetensors";
 
-    // Set the device (use CUDA if available)
-    let device = if tch::Cuda::is_available() {
-        Device::Cuda(0)
-    } else {
-        Device::Cpu
-    };
+impl SimpleNN {
+    // the trained model, loaded once when the player is built
+    pub fn load() -> Result<SimpleNN, Box<dyn Error>> {
+        let model_path = match find_asset_file(MODEL_ASSET) {
+            Some(model_path) => model_path,
+            None => PathBuf::from("src/ai/simple_nn/weights_short.safetensors"),
+        };
+
+        if !model_path.is_file() {
+            return Err(format!("The model of the simple NN player ({}) was not found.", MODEL_ASSET).into())
+        }
+
+        let mut vs = nn::VarStore::new(get_device());
+        let model = SimpleNN::new(&vs.root(), INPUT_SIZE, 1);
+        vs.load(&model_path)?;
+
+        Ok(model)
+    }
+}
 
-    // Load the model
-    let mut vs = nn::VarStore::new(device);
-    let model = SimpleNN::new(&vs.root(), 2368, 1);
-    let model_path = "src/ai/simple_nn/weights_short.safetensors";
-    vs.load(model_path).expect("PyTorch model could not be loaded");
+
+pub fn evaluate_actions(game: &Game, model: &SimpleNN, legal_actions: &Vec<Action>) -> Vec<f32> {
+
+    let device = get_device();
 
     let encoded_actions: Vec<Vec<u32>> = legal_actions.iter()
                                                         .map(|action| {
@@ -182,4 +200,14 @@ pub fn evaluate_actions(game: &Game, legal_actions: &Vec<Action>) -> Vec<f32> {
     formated_actions.iter()
                 .map(|vu| model.forward(&Tensor::from_slice(vu).to(device)).double_value(&[0]) as f32)
                 .collect()
-}
\ No newline at end of file
+}
+
+
+// use CUDA if available
+fn get_device() -> Device {
+    if tch::Cuda::is_available() {
+        Device::Cuda(0)
+    } else {
+        Device::Cpu
+    }
+}
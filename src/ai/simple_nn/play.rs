@@ -1,9 +1,14 @@
-use crate::{ai::simple_nn::evaluate::evaluate_actions, backend::{round::action::Action, setup::game::Game}};
-use rand::{prelude::SliceRandom, thread_rng};
+use crate::{ai::simple_nn::evaluate::evaluate_actions, backend::{round::action::Action, setup::{game::Game, player::PlayerType::SimpleNN}}};
+use rand::prelude::SliceRandom;
 
 pub fn play(game: &Game, legal_actions: Vec<Action>) -> Option<Action> {
     
-    let action_values = evaluate_actions(game, &legal_actions);
+    let model = match &game.parameters.v_players[game.round.active_player as usize].player_type {
+        SimpleNN(model) => model,
+        _ => panic!("The simple NN player has no model."),
+    };
+
+    let action_values = evaluate_actions(game, model, &legal_actions);
 
     let max_value = action_values.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
 
@@ -23,7 +28,7 @@ pub fn play(game: &Game, legal_actions: Vec<Action>) -> Option<Action> {
                                 .map(|i| legal_actions[*i].clone())
                                 .collect();
 
-    let mut rng = thread_rng();
+    let mut rng = game.get_seat_rng();
     let selected_action = best_actions.choose(&mut rng).cloned();
 
     // println!("Dice throws: {:#?}", game.log[game.log.len() - 1].count_dice_draws);
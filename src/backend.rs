@@ -1,7 +1,9 @@
-
 pub mod setup;
 pub mod io;
 
 pub mod logging;
 pub mod round;
-pub mod monte_carlo;
\ No newline at end of file
+pub mod monte_carlo;
+pub mod league;
+pub mod tournament;
+pub mod analysis;
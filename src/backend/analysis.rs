@@ -0,0 +1,180 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ai::classic_player::play::get_rng_probability;
+
+use super::setup::{board::Board, game_parameters::GameParameters, map_generator::get_pips};
+
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct NodeValue {
+    pub node: u32,
+    // expected number of resource cards per dice throw
+    pub production: f64,
+    pub resource_production: Vec<f64>,
+    pub n_tiles: u32,
+    pub harbor_type: Option<u32>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct HarborAccess {
+    pub nodes: (u32, u32),
+    pub harbor_type: u32,
+    // production of the two harbor nodes
+    pub node_production: f64,
+    // board-wide production of the traded resource (2:1 harbors only)
+    pub resource_production: Option<f64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct BoardAnalysis {
+    pub resource_pips: Vec<u32>,
+    pub resource_production: Vec<f64>,
+    pub node_values: Vec<NodeValue>,
+    pub best_spots: Vec<u32>,
+    pub harbor_access: Vec<HarborAccess>,
+    // settlement spots picked greedily in the snake order of the set-up phase, per player
+    pub draft_picks: Vec<Vec<u32>>,
+    pub draft_production: Vec<f64>,
+    // production of the first player minus the mean production of all players after the draft
+    pub first_pick_advantage: f64,
+}
+
+impl BoardAnalysis {
+    pub fn new(board: &Board, parameters: &GameParameters) -> Self {
+
+        let n_resources = parameters.n_resources as usize;
+        let n_players = parameters.n_players as usize;
+
+        // tiles blocked by a robber do not produce
+        let tile_probabilities: Vec<f64> = board.tiles.iter()
+            .map(|tile| {
+                if board.v_robbers.contains(&tile.id) {
+                    return 0.0
+                }
+
+                match (tile.resource, tile.rng) {
+                    (Some(r), Some(n)) if (r as usize) < n_resources => get_rng_probability(n as usize, parameters),
+                    _ => 0.0,
+                }
+            })
+            .collect();
+
+        let mut resource_pips = vec![0; n_resources];
+        let mut resource_production = vec![0.0; n_resources];
+        for (tile, &probability) in board.tiles.iter().zip(tile_probabilities.iter()) {
+            match (tile.resource, tile.rng) {
+                (Some(r), Some(n)) if (r as usize) < n_resources => {
+                    resource_pips[r as usize] += get_pips(n);
+                    resource_production[r as usize] += probability;
+                },
+                _ => (),
+            }
+        }
+
+        let node_values: Vec<NodeValue> = board.nodes.iter()
+            .map(|node| {
+                let mut v_production = vec![0.0; n_resources];
+
+                for &i_tile in &parameters.node_tiles_adjacency[node.id as usize] {
+                    if let Some(r) = board.tiles[i_tile].resource {
+                        if (r as usize) < n_resources {
+                            v_production[r as usize] += tile_probabilities[i_tile];
+                        }
+                    }
+                }
+
+                NodeValue {
+                    node: node.id,
+                    production: v_production.iter().sum(),
+                    resource_production: v_production,
+                    n_tiles: parameters.node_tiles_adjacency[node.id as usize].len() as u32,
+                    harbor_type: node.harbor.as_ref().map(|harbor| harbor.harbor_type),
+                }
+            })
+            .collect();
+
+        let mut best_spots: Vec<u32> = node_values.iter().map(|value| value.node).collect();
+        best_spots.sort_by(|a, b| node_values[*b as usize].production.partial_cmp(&node_values[*a as usize].production).unwrap());
+
+        let harbor_access: Vec<HarborAccess> = match &board.harbors {
+            Some(v_harbors) => v_harbors.iter()
+                .map(|harbor| HarborAccess {
+                    nodes: harbor.nodes,
+                    harbor_type: harbor.harbor_type,
+                    node_production: node_values[harbor.nodes.0 as usize].production + node_values[harbor.nodes.1 as usize].production,
+                    resource_production: if (harbor.harbor_type as usize) < n_resources {
+                        Some(resource_production[harbor.harbor_type as usize])
+                    } else {
+                        None
+                    },
+                })
+                .collect(),
+            None => vec![],
+        };
+
+        let draft_picks = get_draft_picks(board, &best_spots, n_players, parameters.n_setup_rounds as usize);
+
+        let draft_production: Vec<f64> = draft_picks.iter()
+                                            .map(|picks| picks.iter().map(|&i_node| node_values[i_node as usize].production).sum())
+                                            .collect();
+
+        let first_pick_advantage = if draft_production.is_empty() {
+            0.0
+        } else {
+            draft_production[0] - draft_production.iter().sum::<f64>() / draft_production.len() as f64
+        };
+
+        Self {
+            resource_pips,
+            resource_production,
+            node_values,
+            best_spots,
+            harbor_access,
+            draft_picks,
+            draft_production,
+            first_pick_advantage,
+        }
+    }
+}
+
+
+// every player takes the best free spot in the snake order of the set-up rounds, respecting the distance rule
+fn get_draft_picks(board: &Board, best_spots: &Vec<u32>, n_players: usize, n_setup_rounds: usize) -> Vec<Vec<u32>> {
+
+    let mut draft_picks: Vec<Vec<u32>> = vec![vec![]; n_players];
+    let mut blocked = vec![false; board.nodes.len()];
+
+    // spots next to existing buildings are already taken
+    for node in &board.nodes {
+        if node.settlement.is_some() || node.city.is_some() {
+            blocked[node.id as usize] = true;
+            for &neighbor in node.neighbors.iter().flatten() {
+                if (neighbor as usize) < blocked.len() {
+                    blocked[neighbor as usize] = true;
+                }
+            }
+        }
+    }
+
+    for i_round in 0..n_setup_rounds {
+        for i_turn in 0..n_players {
+            let i_player = if i_round % 2 == 0 { i_turn } else { n_players - 1 - i_turn };
+
+            let pick = match best_spots.iter().find(|&&i_node| !blocked[i_node as usize]) {
+                Some(&i_node) => i_node,
+                None => return draft_picks,
+            };
+
+            blocked[pick as usize] = true;
+            for &neighbor in board.nodes[pick as usize].neighbors.iter().flatten() {
+                if (neighbor as usize) < blocked.len() {
+                    blocked[neighbor as usize] = true;
+                }
+            }
+
+            draft_picks[i_player].push(pick);
+        }
+    }
+
+    draft_picks
+}
@@ -1,6 +1,10 @@
 
 pub mod read_parameters;
+pub mod template;
+pub mod template_registry;
+pub mod assets;
 mod save_summary;
+mod save_analysis;
 pub mod encode_log;
 pub mod save_board;
 pub mod save_round;
@@ -0,0 +1,74 @@
+use std::{env, error::Error, fs, path::PathBuf};
+
+
+// files of data/, compiled into the binary
+macro_rules! embed_asset {
+    ($path:literal) => {
+        ($path, include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/data/", $path)))
+    };
+}
+
+// assets every game needs, available from any working directory. The templates are embedded by the template registry.
+// The weights of the classic players are the untrained ones: trained weights go into the override directory
+pub const EMBEDDED_ASSETS: [(&str, &[u8]); 7] = [
+    embed_asset!("parameters/tile_colors.csv"),
+    embed_asset!("parameters/player_colors.csv"),
+    embed_asset!("parameters/resources.csv"),
+    embed_asset!("parameters/harbor_symbols.csv"),
+    embed_asset!("fonts/DelaGothicOne-Regular.ttf"),
+    embed_asset!("ai/classic/weights.csv"),
+    embed_asset!("ai/classic_plus/weights.csv"),
+];
+
+// a directory laid out like data/ whose files take precedence over the embedded ones
+pub const ASSET_DIR_VARIABLE: &str = "CATAN_ASSET_DIR";
+
+// directory of the intermediate SVG files of the board drawing (data/assets/ inside a checkout)
+pub const DRAWING_DIR_VARIABLE: &str = "CATAN_DRAWING_DIR";
+
+
+// an asset by its path relative to data/: the override directory, the embedded assets and data/ of the working directory
+// (for assets that are not embedded, e.g. the model of the simple NN player)
+pub fn read_asset(path: &str) -> Result<Vec<u8>, Box<dyn Error>> {
+    if let Some(file_path) = get_override_dir().map(|dir| dir.join(path)).filter(|file_path| file_path.is_file()) {
+        return Ok(fs::read(file_path)?)
+    }
+
+    match EMBEDDED_ASSETS.iter().find(|(embedded_path, _)| *embedded_path == path) {
+        Some((_, content)) => Ok(content.to_vec()),
+        None => match fs::read(PathBuf::from("data").join(path)) {
+            Ok(content) => Ok(content),
+            Err(e) => Err(format!("Asset {} not found (set {} to a directory containing it): {}", path, ASSET_DIR_VARIABLE, e).into()),
+        },
+    }
+}
+
+pub fn read_asset_to_string(path: &str) -> Result<String, Box<dyn Error>> {
+    Ok(String::from_utf8(read_asset(path)?)?)
+}
+
+// for loaders that need a file instead of its content: the override directory, then data/ of the working directory
+pub fn find_asset_file(path: &str) -> Option<PathBuf> {
+    let mut candidates: Vec<PathBuf> = get_override_dir().map(|dir| dir.join(path)).into_iter().collect();
+    candidates.push(PathBuf::from("data").join(path));
+
+    candidates.into_iter().find(|file_path| file_path.is_file())
+}
+
+pub fn get_override_dir() -> Option<PathBuf> {
+    env::var_os(ASSET_DIR_VARIABLE).map(PathBuf::from)
+}
+
+// where the board is drawn before its layers are combined: $CATAN_DRAWING_DIR, data/assets/ of the working directory
+// or a directory in the system's temporary directory
+pub fn get_drawing_path(file_name: &str) -> String {
+    let dir = match env::var_os(DRAWING_DIR_VARIABLE) {
+        Some(dir) => PathBuf::from(dir),
+        None if PathBuf::from("data/assets").is_dir() => PathBuf::from("data/assets"),
+        None => env::temp_dir().join("catan-cli"),
+    };
+
+    fs::create_dir_all(&dir).expect("Unable to create the drawing directory");
+
+    dir.join(file_name).to_string_lossy().to_string()
+}
@@ -1,8 +1,28 @@
 use std::error::Error;
 
-use crate::backend::{logging::log_entry::LogEntry, round::{action::Action, cards::CardType, outcome::Outcome, phase::Phase}, setup::{edge::make_edge, game::Game, node_status::{self, NodeStatus}}};
+use crate::backend::{logging::log_entry::LogEntry, round::{action::Action, cards::CardType, cities_knights::{ProgressCard, N_COMMODITIES, N_PROGRESS_CARD_TYPES}, outcome::Outcome, phase::Phase, pieces::{get_remaining_pieces, get_remaining_ships}}, setup::{edge::make_edge, game::Game, node_status::{self, NodeStatus}, ruleset::Ruleset}};
+
+// layout of the hot encoding. Base is the layout the simple network was trained on, Extended adds fields
+// for the phases, actions and pieces of the variants and expansions
+#[derive(Debug, Clone, PartialEq)]
+pub enum LogEncoding {
+    Base,
+    Extended,
+}
+
 
 impl Game {
+    // games the base layout cannot tell apart from the base game are always encoded with the extended layout
+    pub fn get_log_encoding(&self) -> LogEncoding {
+        match self.parameters.log_encoding {
+            LogEncoding::Extended => LogEncoding::Extended,
+            LogEncoding::Base if self.parameters.special_building_phase || self.parameters.init_v_pirate.is_some() => LogEncoding::Extended,
+            LogEncoding::Base if self.parameters.has_sea() || self.parameters.has_gold() => LogEncoding::Extended,
+            LogEncoding::Base if self.parameters.ruleset == Ruleset::CitiesAndKnights => LogEncoding::Extended,
+            LogEncoding::Base => LogEncoding::Base,
+        }
+    }
+
     pub fn encode_log(&self) -> Result<(), Box<dyn Error>> {
 
         let file_path = "data/saves/".to_string() + &self.parameters.title.clone().unwrap_or("untitled".to_string());
@@ -17,6 +37,8 @@ impl Game {
 
         let mut v_out: Vec<u32> = vec![];
 
+        let encoding = self.get_log_encoding();
+
         let final_scores: Vec<u32>;
         let length_log = self.log.len();
 
@@ -87,8 +109,11 @@ impl Game {
             } // n_dice_outcomes fields
 
 
-            // phase
-            let n_phase = 9;
+            // phase, with the codes of the round logs (save_round). The base layout has no special building or gold phase
+            let n_phase = match encoding {
+                LogEncoding::Base => 9,
+                LogEncoding::Extended => 11,
+            };
 
             let current_phase = match &round.phase {
                 Phase::SetUp => 0,
@@ -100,6 +125,8 @@ impl Game {
                 Phase::Building => 6,
                 Phase::SecondCardPhase => 7,
                 Phase::Terminal => 8,
+                Phase::SpecialBuilding => 9,
+                Phase::GoldChoice => 10,
             };
 
             for i_phase in 0..n_phase {
@@ -108,11 +135,15 @@ impl Game {
                 } else {
                     row.push(0);
                 }
-            } // 9 fields
+            } // n_phase fields
 
-            // action_type
-            let n_actions = 19 as usize;
+            // action_type, with the codes of the round logs (save_round). The base layout has no code for the moves of the variants
+            let n_actions = match encoding {
+                LogEncoding::Base => 19,
+                LogEncoding::Extended => 29,
+            } as usize;
             let n_nodes = round.board.nodes.len();
+            let n_edges = self.parameters.edge_map.len();
             let n_robbers = round.board.v_robbers.len();
             let n_tiles = round.board.tiles.len();
             let n_players = self.parameters.n_players as usize;
@@ -196,6 +227,34 @@ impl Game {
                             
                             row.extend(vec![0; n_action_fields - n_actions - 2 * n_nodes - n_robbers - n_tiles - n_players])
                         },
+                        Action::Pirate(_, _, i_victim) => {
+                            // pirate and edge go into the extended fields below
+                            for i_action in 0..n_actions {
+                                if i_action == 19 {
+                                    row.push(1)
+                                } else {
+                                    row.push(0)
+                                }
+                            }
+
+                            // i_settle, i_road, i_robber and i_tile
+                            row.extend(vec![0; 2 * n_nodes + n_robbers + n_tiles]);
+
+                            // i_victim 
+                            if let Some(victim) = *i_victim {
+                                for iv in 0..n_players as u32 {
+                                    if iv == victim {
+                                        row.push(1);
+                                    } else {
+                                        row.push(0);
+                                    }
+                                }
+                            } else {
+                                row.extend(vec![0; n_players]);
+                            }
+                            
+                            row.extend(vec![0; n_action_fields - n_actions - 2 * n_nodes - n_robbers - n_tiles - n_players])
+                        },
                         Action::DiscardCards(v_discard) => {
                             for i_action in 0..n_actions {
                                 if i_action == 2 {
@@ -207,7 +266,43 @@ impl Game {
 
                             row.extend(vec![0; n_action_fields - n_resources - n_actions]);
 
-                            row.extend(v_discard);
+                            // discarded commodities (Cities & Knights) go into the extended fields below
+                            row.extend(v_discard.iter().take(n_resources));
+                        },
+                        Action::ChooseGold(v_chosen) => {
+                            for i_action in 0..n_actions {
+                                if i_action == 22 {
+                                    row.push(1)
+                                } else {
+                                    row.push(0)
+                                }
+                            }
+
+                            // chosen resources in the resource fields of discards
+                            row.extend(vec![0; n_action_fields - n_resources - n_actions]);
+
+                            row.extend(v_chosen);
+                        },
+                        Action::BuildKnight(_) | Action::ActivateKnight(_) | Action::PromoteKnight(_) | Action::BuildImprovement(_) | Action::PlayProgressCard(_) | Action::CommodityTrade(_, _) => {
+                            let action_code = match action {
+                                Action::BuildKnight(_) => 23,
+                                Action::ActivateKnight(_) => 24,
+                                Action::PromoteKnight(_) => 25,
+                                Action::BuildImprovement(_) => 26,
+                                Action::PlayProgressCard(_) => 27,
+                                _ => 28,
+                            };
+
+                            for i_action in 0..n_actions {
+                                if i_action == action_code {
+                                    row.push(1)
+                                } else {
+                                    row.push(0)
+                                }
+                            }
+
+                            // knights, tracks, cards and commodities go into the extended fields below
+                            row.extend(vec![0; n_action_fields - n_actions]);
                         },
                         Action::NoDiscard => {
                             for i_action in 0..n_actions {
@@ -513,6 +608,23 @@ impl Game {
 
                             row.extend(vec![0; n_action_fields - n_actions - (3 + 8 * n_nodes + n_harbor_types + n_robbers + n_tiles + n_players + 9 * n_resources)])
                         },
+                        Action::BuildShip(_, _) | Action::MoveShip(_, _, _, _) => {
+                            let action_code = match action {
+                                Action::BuildShip(_, _) => 20,
+                                _ => 21,
+                            };
+
+                            for i_action in 0..n_actions {
+                                if i_action == action_code {
+                                    row.push(1)
+                                } else {
+                                    row.push(0)
+                                }
+                            }
+
+                            // edges go into the extended fields below
+                            row.extend(vec![0; n_action_fields - n_actions]);
+                        },
                         Action::BuildSettlement(settle_node) => {
                             for i_action in 0..n_actions {
                                 if i_action == 13 {
@@ -606,6 +718,92 @@ impl Game {
                 },
             }
 
+            // pirate and edge of a pirate move, origin and destination edge of a ship
+            if encoding == LogEncoding::Extended {
+                let n_pirates = round.board.v_pirates.len();
+
+                let get_edge_index = |start_node: u32, end_node: u32| self.parameters.edge_map.get(&make_edge(start_node, end_node)).copied();
+
+                let ship_edges = match &round.action {
+                    Some(Action::BuildShip(s, e)) => (None, get_edge_index(*s, *e)),
+                    Some(Action::MoveShip(s1, e1, s2, e2)) => (get_edge_index(*s1, *e1), get_edge_index(*s2, *e2)),
+                    _ => (None, None),
+                };
+
+                match &round.action {
+                    Some(Action::Pirate(i_pirate, i_edge, _)) => {
+                        for ip in 0..n_pirates as u32 {
+                            if ip == *i_pirate {
+                                row.push(1);
+                            } else {
+                                row.push(0);
+                            }
+                        }
+
+                        for ie in 0..n_edges as u32 {
+                            if ie == *i_edge {
+                                row.push(1);
+                            } else {
+                                row.push(0);
+                            }
+                        }
+                    },
+                    _ => row.extend(vec![0; n_pirates + n_edges]),
+                }
+
+                for ship_edge in [ship_edges.0, ship_edges.1] {
+                    for i_edge in 0..n_edges {
+                        if ship_edge == Some(i_edge) {
+                            row.push(1);
+                        } else {
+                            row.push(0);
+                        }
+                    }
+                }
+
+                // knight node, improvement track or commodity, progress card with its roads and resource (Cities & Knights)
+                let knight_node = match &round.action {
+                    Some(Action::BuildKnight(i_node) | Action::ActivateKnight(i_node) | Action::PromoteKnight(i_node)) => Some(*i_node as usize),
+                    _ => None,
+                };
+
+                let i_commodity = match &round.action {
+                    Some(Action::BuildImprovement(i) | Action::CommodityTrade(i, _) | Action::PlayProgressCard(ProgressCard::TradeMonopoly(i))) => Some(*i as usize),
+                    _ => None,
+                };
+
+                let progress_card = match &round.action {
+                    Some(Action::PlayProgressCard(card)) => Some(card.get_id()),
+                    _ => None,
+                };
+
+                let road_edges = match &round.action {
+                    Some(Action::PlayProgressCard(ProgressCard::RoadBuilding(s1, e1, s2, e2))) => (get_edge_index(*s1, *e1), get_edge_index(*s2, *e2)),
+                    _ => (None, None),
+                };
+
+                let i_resource = match &round.action {
+                    Some(Action::PlayProgressCard(ProgressCard::ResourceMonopoly(r)) | Action::CommodityTrade(_, r)) => Some(*r as usize),
+                    _ => None,
+                };
+
+                for (index, n_fields) in [(knight_node, n_nodes), (i_commodity, N_COMMODITIES), (progress_card, N_PROGRESS_CARD_TYPES), (road_edges.0, n_edges), (road_edges.1, n_edges), (i_resource, n_resources)] {
+                    for i_field in 0..n_fields {
+                        if index == Some(i_field) {
+                            row.push(1);
+                        } else {
+                            row.push(0);
+                        }
+                    }
+                }
+
+                // discarded commodities follow the discarded resources
+                match &round.action {
+                    Some(Action::DiscardCards(v_discard)) if v_discard.len() > n_resources => row.extend(v_discard.iter().skip(n_resources)),
+                    _ => row.extend(vec![0; N_COMMODITIES]),
+                }
+            } // n_pirates + 5 * n_edges + n_nodes + 2 * N_COMMODITIES + N_PROGRESS_CARD_TYPES + n_resources fields (extended layout)
+
             // budget of active player
             for i_resource in 0..n_resources {
                 row.push(round.board.budgets[active_player][i_resource]);
@@ -637,6 +835,14 @@ impl Game {
                 }
             }
 
+            // remaining roads, settlements, cities and ships of every player
+            if encoding == LogEncoding::Extended {
+                for i_player in 0..n_players as u32 {
+                    row.extend(get_remaining_pieces(i_player, &round.board, &self.parameters.max_pieces));
+                    row.push(get_remaining_ships(i_player, &round.board, self.parameters.max_ships));
+                }
+            } // 4 * n_players fields (extended layout)
+
             // for each node: status and owner
             let n_node_stati = 4;
             for i_node in 0..n_nodes {
@@ -670,7 +876,6 @@ impl Game {
                 }
             } // 4 * n_nodes + n_players * n_nodes
 
-            let n_edges = self.parameters.edge_map.len();
             for i_edge in 0..n_edges {
                 if let Some(roads) = &round.board.roads {
                     let mut matched = false;
@@ -761,6 +966,79 @@ impl Game {
                 }
             } // 
 
+            // pirates (extended layout)
+            if encoding == LogEncoding::Extended {
+                for i_pirate in &round.board.v_pirates {
+                    for i_edge in 0..n_edges as u32 {
+                        if i_edge == *i_pirate {
+                            row.push(1);
+                        } else {
+                            row.push(0);
+                        }
+                    }
+                }
+            } // n_pirates * n_edges
+
+            // owner of the ship on each edge (extended layout)
+            if encoding == LogEncoding::Extended {
+                for i_edge in 0..n_edges {
+                    let owner = round.board.ships.iter()
+                        .find(|ship| self.parameters.edge_map.get(&make_edge(ship.nodes.0, ship.nodes.1)) == Some(&i_edge))
+                        .map(|ship| ship.player);
+
+                    for i_player in 0..n_players as u32 {
+                        if owner == Some(i_player) {
+                            row.push(1);
+                        } else {
+                            row.push(0);
+                        }
+                    }
+                }
+            } // n_edges * n_players
+
+            // Cities & Knights (extended layout): commodities and progress cards of the active player, and for every player the improvements,
+            // the number of commodities and progress cards held, progress and defender points. Then the knights, metropolises, barbarians and progress decks
+            if encoding == LogEncoding::Extended {
+                row.extend(&round.board.commodities[active_player]);
+                row.extend(&round.board.progress_cards[active_player]);
+
+                for i_player in 0..n_players {
+                    row.extend(&round.board.improvements[i_player]);
+                    row.push(round.board.commodities[i_player].iter().sum());
+                    row.push(round.board.progress_cards[i_player].iter().sum());
+                    row.push(round.board.progress_points[i_player]);
+                    row.push(round.board.defender_points[i_player]);
+                }
+
+                for i_node in 0..n_nodes as u32 {
+                    let knight = round.board.knights.iter().find(|knight| knight.node == i_node);
+
+                    for i_player in 0..n_players as u32 {
+                        if knight.map(|knight| knight.player) == Some(i_player) {
+                            row.push(1);
+                        } else {
+                            row.push(0);
+                        }
+                    }
+
+                    row.push(knight.map(|knight| knight.level).unwrap_or(0));
+                    row.push(knight.map(|knight| knight.active as u32).unwrap_or(0));
+                }
+
+                for holder in &round.board.metropolises {
+                    for i_player in 0..n_players as u32 {
+                        if *holder == Some(i_player) {
+                            row.push(1);
+                        } else {
+                            row.push(0);
+                        }
+                    }
+                }
+
+                row.push(round.board.barbarian_position);
+                row.extend(&round.board.progress_deck);
+            } // N_COMMODITIES + N_PROGRESS_CARD_TYPES + n_players * (N_COMMODITIES + 4) + n_nodes * (n_players + 2) + N_COMMODITIES * n_players + 1 + N_COMMODITIES
+
             for tile in &round.board.tiles {
                 if let Some(tile_rng) = tile.rng {
                     for i_dice in 0..n_dice_outcomes {
@@ -77,6 +77,65 @@ pub fn read_harbors_csv(file_path: &str) -> Result<Vec<(u32, u32, u32)>, Box<dyn
     Ok(harbor_values)
 }
 
+// a matrix with named rows (first column) and named columns, e.g. the building costs per resource.
+// rows and columns may come in any order, but every name has to be given exactly once
+pub fn read_named_matrix_csv(file_path: &str, row_names: &[&str], column_names: &[&str]) -> Result<Vec<Vec<u32>>, Box<dyn Error>> {
+    let content = std::fs::read_to_string(file_path)?;
+
+    read_named_matrix_str(&content, file_path, row_names, column_names)
+}
+
+// as read_named_matrix_csv, for CSV content that is already in memory. The label names the content in error messages
+pub fn read_named_matrix_str(content: &str, file_path: &str, row_names: &[&str], column_names: &[&str]) -> Result<Vec<Vec<u32>>, Box<dyn Error>> {
+    let mut rdr = ReaderBuilder::new()
+                            .delimiter(b',')
+                            .has_headers(true)
+                            .from_reader(content.as_bytes());
+
+    let headers = rdr.headers()?.clone();
+
+    let mut i_columns: Vec<usize> = vec![];
+    for header in headers.iter().skip(1) {
+        match column_names.iter().position(|name| name.eq_ignore_ascii_case(header.trim())) {
+            Some(i_column) if !i_columns.contains(&i_column) => i_columns.push(i_column),
+            Some(_) => return Err(format!("{}: column {} is given twice.", file_path, header).into()),
+            None => return Err(format!("{}: unknown column {}.", file_path, header).into()),
+        }
+    }
+
+    if i_columns.len() != column_names.len() {
+        return Err(format!("{}: expected the columns {:?}.", file_path, column_names).into());
+    }
+
+    let mut matrix: Vec<Option<Vec<u32>>> = vec![None; row_names.len()];
+
+    for result in rdr.records() {
+        let record = result?;
+        let row_name = record.get(0).unwrap_or("").trim();
+
+        let i_row = match row_names.iter().position(|name| name.eq_ignore_ascii_case(row_name)) {
+            Some(i_row) => i_row,
+            None => return Err(format!("{}: unknown row {}.", file_path, row_name).into()),
+        };
+
+        if matrix[i_row].is_some() {
+            return Err(format!("{}: row {} is given twice.", file_path, row_name).into());
+        }
+
+        let mut row = vec![0; column_names.len()];
+        for (value, &i_column) in record.iter().skip(1).zip(i_columns.iter()) {
+            row[i_column] = value.trim().parse::<u32>()?;
+        }
+
+        matrix[i_row] = Some(row);
+    }
+
+    matrix.into_iter()
+        .zip(row_names.iter())
+        .map(|(row, name)| row.ok_or_else(|| format!("{}: row {} is missing.", file_path, name).into()))
+        .collect()
+}
+
 pub fn read_deserialized_csv<T: 'static>(file_path: &str) -> Result<Vec<T>, Box<dyn Error>>
 where 
     T: for<'a> Deserialize<'a>,
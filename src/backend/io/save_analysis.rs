@@ -0,0 +1,129 @@
+use std::{error::Error, fs::{self, File}};
+
+use csv::Writer;
+
+use crate::backend::analysis::BoardAnalysis;
+
+impl BoardAnalysis {
+    pub fn write_to_csv(&self, file_path: &str) -> Result<(), Box<dyn Error>> {
+
+        fs::create_dir_all(file_path)?;
+
+        self.write_resources(file_path)?;
+        self.write_nodes(file_path)?;
+        self.write_harbors(file_path)?;
+        self.write_draft(file_path)?;
+
+        Ok(())
+    }
+
+    fn write_resources(&self, file_path: &str) -> Result<(), Box<dyn Error>> {
+        let file_path = file_path.to_owned() + "/resources.csv";
+
+        let mut wtr = Writer::from_writer(File::create(file_path)?);
+
+        wtr.write_record(&["resource", "pips", "production"])?;
+
+        for (i_resource, (pips, production)) in self.resource_pips.iter().zip(self.resource_production.iter()).enumerate() {
+            wtr.write_record(&[i_resource.to_string(), pips.to_string(), production.to_string()])?;
+        }
+
+        wtr.flush()?;
+
+        Ok(())
+    }
+
+    fn write_nodes(&self, file_path: &str) -> Result<(), Box<dyn Error>> {
+        let file_path = file_path.to_owned() + "/nodes.csv";
+
+        let mut wtr = Writer::from_writer(File::create(file_path)?);
+
+        let mut headers = vec![
+            "node".to_string(),
+            "rank".to_string(),
+            "production".to_string(),
+            "n_tiles".to_string(),
+            "harbor_type".to_string(),
+        ];
+
+        let n_resources = self.resource_pips.len();
+        for i_resource in 0..n_resources {
+            headers.push(format!("production_{}", i_resource));
+        }
+
+        wtr.write_record(&headers)?;
+
+        for node_value in &self.node_values {
+            let rank = self.best_spots.iter().position(|&i_node| i_node == node_value.node).unwrap_or(0);
+
+            let mut record = vec![
+                node_value.node.to_string(),
+                rank.to_string(),
+                node_value.production.to_string(),
+                node_value.n_tiles.to_string(),
+                match node_value.harbor_type {
+                    Some(h) => h.to_string(),
+                    None => "NaN".to_string(),
+                },
+            ];
+
+            for production in &node_value.resource_production {
+                record.push(production.to_string());
+            }
+
+            wtr.write_record(&record)?;
+        }
+
+        wtr.flush()?;
+
+        Ok(())
+    }
+
+    fn write_harbors(&self, file_path: &str) -> Result<(), Box<dyn Error>> {
+        let file_path = file_path.to_owned() + "/harbors.csv";
+
+        let mut wtr = Writer::from_writer(File::create(file_path)?);
+
+        wtr.write_record(&["first_node", "second_node", "harbor_type", "node_production", "resource_production"])?;
+
+        for harbor in &self.harbor_access {
+            wtr.write_record(&[
+                harbor.nodes.0.to_string(),
+                harbor.nodes.1.to_string(),
+                harbor.harbor_type.to_string(),
+                harbor.node_production.to_string(),
+                match harbor.resource_production {
+                    Some(p) => p.to_string(),
+                    None => "NaN".to_string(),
+                },
+            ])?;
+        }
+
+        wtr.flush()?;
+
+        Ok(())
+    }
+
+    fn write_draft(&self, file_path: &str) -> Result<(), Box<dyn Error>> {
+        let file_path = file_path.to_owned() + "/draft.csv";
+
+        let mut wtr = Writer::from_writer(File::create(file_path)?);
+
+        wtr.write_record(&["player_id", "nodes", "production", "first_pick_advantage"])?;
+
+        for (i_player, (picks, production)) in self.draft_picks.iter().zip(self.draft_production.iter()).enumerate() {
+            let nodes: Vec<String> = picks.iter().map(|i_node| i_node.to_string()).collect();
+
+            wtr.write_record(&[
+                i_player.to_string(),
+                nodes.join(" "),
+                production.to_string(),
+                self.first_pick_advantage.to_string(),
+            ])?;
+        }
+
+        wtr.flush()?;
+
+        Ok(())
+    }
+}
@@ -2,7 +2,7 @@ use std::{error::Error, fs::OpenOptions, io::{BufReader, Read}, path::Path};
 
 use csv::Writer;
 
-use crate::backend::setup::{game::Game, node_status::NodeStatus, shape::{get_n_node, TileShape}};
+use crate::backend::setup::{game::Game, node_status::NodeStatus, shape::get_n_node};
 
 impl Game {
     pub fn write_board_to_csv(&self, file_path: &str) -> Result<(), Box<dyn Error>> {
@@ -163,7 +163,7 @@ impl Game {
         }
 
         let n_logs = self.log.len();
-        let n_nodes = get_n_node(self.parameters.n_tile_rings, &TileShape::Hexagon) as usize;
+        let n_nodes = get_n_node(self.parameters.n_tile_rings, &self.parameters.tile_shape) as usize;
 
         for log_id in 0..n_logs { 
             for node_id in 0..n_nodes {
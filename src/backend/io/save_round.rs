@@ -2,7 +2,7 @@ use std::{error::Error, fs::{File, OpenOptions}, io::{BufReader, Read}, path::Pa
 
 use csv::Writer;
 
-use crate::backend::{round::{action::Action, cards::CardType, outcome::Outcome, phase::Phase}, setup::{edge::make_edge, game::Game, node_status::NodeStatus, shape::get_n_tiles}};
+use crate::backend::{round::{action::Action, cards::CardType, cities_knights::ProgressCard, outcome::Outcome, phase::Phase, pieces::get_remaining_pieces}, setup::{edge::make_edge, game::Game, node_status::NodeStatus, rng_service::Stream, shape::get_n_tiles}};
 
 
 impl Game {
@@ -117,7 +117,11 @@ impl Game {
                     headers.push(format!("p{}_public_cards_{}", i_player, i))
                 }
                 
-                headers.push(format!("p{}_longest_road", i_player))
+                headers.push(format!("p{}_longest_road", i_player));
+
+                headers.push(format!("p{}_remaining_roads", i_player));
+                headers.push(format!("p{}_remaining_settlements", i_player));
+                headers.push(format!("p{}_remaining_cities", i_player));
             }
 
             let n_nodes = self.parameters.node_adjacency.len();
@@ -182,6 +186,8 @@ impl Game {
                     Phase::Building => row.push(6.to_string()),
                     Phase::SecondCardPhase => row.push(7.to_string()),
                     Phase::Terminal => row.push(8.to_string()),
+                    Phase::SpecialBuilding => row.push(9.to_string()),
+                    Phase::GoldChoice => row.push(10.to_string()),
                 }
 
                 row.push(round.phase_count.to_string());
@@ -221,10 +227,31 @@ impl Game {
                                 
                                 row.extend(vec![String::new(); (n_action_fields - 5) as usize])
                             },
+                            Action::Pirate(i_pirate, i_edge, i_victim) => {
+                                // pirate and edge go into the robber and tile columns
+                                row.push(19.to_string());
+                                row.extend(vec![String::new(); 2]);
+                                row.push(i_pirate.to_string());
+                                row.push(i_edge.to_string());
+                                if let Some(victim) = i_victim {
+                                    row.push(victim.to_string());
+                                } else {
+                                    row.extend(vec![String::new()])
+                                }
+                                
+                                row.extend(vec![String::new(); (n_action_fields - 5) as usize])
+                            },
                             Action::DiscardCards(v_discard) => {
                                 row.push(2.to_string());
                                 row.extend(vec![String::new(); (n_action_fields - self.parameters.n_resources) as usize]);
-                                row.extend(v_discard.iter().map(|d| d.to_string()));
+                                // discarded commodities (Cities & Knights) are only in the hot encoding
+                                row.extend(v_discard.iter().take(self.parameters.n_resources as usize).map(|d| d.to_string()));
+                            },
+                            Action::ChooseGold(v_chosen) => {
+                                // chosen resources go into the discard columns
+                                row.push(22.to_string());
+                                row.extend(vec![String::new(); (n_action_fields - self.parameters.n_resources) as usize]);
+                                row.extend(v_chosen.iter().map(|c| c.to_string()));
                             },
                             Action::NoDiscard => {
                                 row.push(3.to_string());
@@ -324,6 +351,24 @@ impl Game {
                                 row.push(e1.to_string());
                                 row.extend(vec![String::new(); (n_action_fields - 25) as usize]);
                             },
+                            Action::BuildShip(s1, e1) => {
+                                // ships go into the road columns
+                                row.push(20.to_string());
+                                row.extend(vec![String::new(); 23]);
+                                row.push(s1.to_string());
+                                row.push(e1.to_string());
+                                row.extend(vec![String::new(); (n_action_fields - 25) as usize]);
+                            },
+                            Action::MoveShip(s0, e0, s1, e1) => {
+                                // the old position goes into the setup columns, the new one into the road columns
+                                row.push(21.to_string());
+                                row.push(s0.to_string());
+                                row.push(e0.to_string());
+                                row.extend(vec![String::new(); 21]);
+                                row.push(s1.to_string());
+                                row.push(e1.to_string());
+                                row.extend(vec![String::new(); (n_action_fields - 25) as usize]);
+                            },
                             Action::BuildSettlement(node) => {
                                 row.push(13.to_string());
                                 row.extend(vec![String::new(); 25]);
@@ -336,6 +381,50 @@ impl Game {
                                 row.push(node.to_string());
                                 row.extend(vec![String::new(); (n_action_fields - 26) as usize]);
                             },
+                            Action::BuildKnight(node) | Action::ActivateKnight(node) | Action::PromoteKnight(node) | Action::BuildImprovement(node) => {
+                                // knight nodes and improvement tracks go into the building column
+                                let code = match action {
+                                    Action::BuildKnight(_) => 23,
+                                    Action::ActivateKnight(_) => 24,
+                                    Action::PromoteKnight(_) => 25,
+                                    _ => 26,
+                                };
+                                row.push(code.to_string());
+                                row.extend(vec![String::new(); 25]);
+                                row.push(node.to_string());
+                                row.extend(vec![String::new(); (n_action_fields - 26) as usize]);
+                            },
+                            Action::PlayProgressCard(card) => {
+                                // the card type goes into the building column, its roads and resource or commodity into the roads and monopoly columns
+                                row.push(27.to_string());
+
+                                match card {
+                                    ProgressCard::RoadBuilding(s1, e1, s2, e2) => {
+                                        row.extend(vec![String::new(); 5]);
+                                        row.extend(vec![s1.to_string(), e1.to_string(), s2.to_string(), e2.to_string()]);
+                                        row.extend(vec![String::new(); 16]);
+                                    },
+                                    ProgressCard::ResourceMonopoly(i_monopoly) | ProgressCard::TradeMonopoly(i_monopoly) => {
+                                        row.extend(vec![String::new(); 11]);
+                                        row.push(i_monopoly.to_string());
+                                        row.extend(vec![String::new(); 13]);
+                                    },
+                                    _ => {
+                                        row.extend(vec![String::new(); 25]);
+                                    },
+                                }
+
+                                row.push(card.get_id().to_string());
+                                row.extend(vec![String::new(); (n_action_fields - 26) as usize]);
+                            },
+                            Action::CommodityTrade(i_commodity, rd) => {
+                                // the commodity goes into the supplied column of bank trades
+                                row.push(28.to_string());
+                                row.extend(vec![String::new(); 12]);
+                                row.push(i_commodity.to_string());
+                                row.push(rd.to_string());
+                                row.extend(vec![String::new(); (n_action_fields - 14) as usize]);
+                            },
                             Action::BuyDevCard => {
                                 row.push(15.to_string());
                                 row.extend(vec![String::new(); n_action_fields as usize])
@@ -386,6 +475,10 @@ impl Game {
                     } else {
                         row.push(String::new());
                     }
+
+                    for rp in get_remaining_pieces(i_player as u32, &round.board, &self.parameters.max_pieces) {
+                        row.push(rp.to_string());
+                    }
                 }
 
                 
@@ -525,6 +618,8 @@ impl Game {
         self.write_round_build_road(file_path)?;
 
         self.write_round_buildings(file_path)?;
+
+        self.write_round_rng(file_path)?;
         
         Ok(())
     }
@@ -534,7 +629,7 @@ impl Game {
         let (mut wtr, file_empty) = open_writer(file_path, "logs.csv")?;
 
         if file_empty {
-            let headers = vec![
+            let mut headers = vec![
                 "game_id".to_string(),
                 "log_id".to_string(),
                 "dice_outcome".to_string(),
@@ -544,12 +639,17 @@ impl Game {
                 "phase".to_string(),
                 "phase_count".to_string(),
                 "card_count".to_string(),
+                "n_undrawn_dev_cards".to_string(),
                 "robber_count".to_string(),
                 "action".to_string(),
                 "count_dice_draws".to_string(),
                 "duration".to_string()
             ];
 
+            for i in 0..self.parameters.n_resources {
+                headers.push(format!("bank_stock_{}", i))
+            }
+
             wtr.write_record(&headers)?;
         }
 
@@ -584,12 +684,16 @@ impl Game {
                     Phase::Building => row.push(6.to_string()),
                     Phase::SecondCardPhase => row.push(7.to_string()),
                     Phase::Terminal => row.push(8.to_string()),
+                    Phase::SpecialBuilding => row.push(9.to_string()),
+                    Phase::GoldChoice => row.push(10.to_string()),
                 }
 
                 row.push(round.phase_count.to_string());
 
                 row.push(round.card_count.to_string());
 
+                row.push(round.board.get_n_undrawn_dev_cards().to_string());
+
                 row.push(round.robber_count.to_string());
 
                 match &round.action {
@@ -614,6 +718,16 @@ impl Game {
                             Action::FinishRound => row.push(16.to_string()),
                             Action::Save => row.push(17.to_string()),
                             Action::Quit => row.push(18.to_string()),
+                            Action::Pirate(_, _, _) => row.push(19.to_string()),
+                            Action::BuildShip(_, _) => row.push(20.to_string()),
+                            Action::MoveShip(_, _, _, _) => row.push(21.to_string()),
+                            Action::ChooseGold(_) => row.push(22.to_string()),
+                            Action::BuildKnight(_) => row.push(23.to_string()),
+                            Action::ActivateKnight(_) => row.push(24.to_string()),
+                            Action::PromoteKnight(_) => row.push(25.to_string()),
+                            Action::BuildImprovement(_) => row.push(26.to_string()),
+                            Action::PlayProgressCard(_) => row.push(27.to_string()),
+                            Action::CommodityTrade(_, _) => row.push(28.to_string()),
                         }
                     },
                     None => row.extend(vec![String::new()]),
@@ -627,6 +741,10 @@ impl Game {
                     row.extend(vec![String::new()]);
                 }
 
+                for stock in &round.board.bank_stock {
+                    row.push(stock.to_string());
+                }
+
                 wtr.write_record(row)?;
             }
         }
@@ -1205,6 +1323,51 @@ impl Game {
         Ok(())
     }
 
+
+    // state of every random stream, so a game can be resumed from any log entry with identical draws
+    fn write_round_rng(&self, file_path: &str) -> Result<(), Box<dyn Error>> {
+        let (mut wtr, file_empty) = open_writer(file_path, "rng_streams.csv")?;
+
+        if file_empty {
+            let headers = vec![
+                "game_id".to_string(),
+                "log_id".to_string(),
+                "stream".to_string(),
+                "seed".to_string(),
+                "n_draws".to_string()
+            ];
+
+            wtr.write_record(&headers)?;
+        }
+
+        let n_logs = self.log.len();
+
+        for i_log in 0..n_logs {
+            if let Some(round) = &self.log[i_log].round {
+                for (stream, seed, n_draws) in round.board.rng.get_streams() {
+                    // the seats do not advance their streams: their draw is indexed by the length of the log
+                    let i_draw = match stream {
+                        Stream::Seat(_) => i_log as u64,
+                        _ => *n_draws,
+                    };
+
+                    let row = vec![
+                        self.parameters.id.clone(),
+                        i_log.to_string(),
+                        stream.name(),
+                        seed.to_string(),
+                        i_draw.to_string(),
+                    ];
+
+                    wtr.write_record(row)?;
+                }
+            }
+        }
+
+        wtr.flush()?;
+
+        Ok(())
+    }
 }
 
 
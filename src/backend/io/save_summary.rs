@@ -54,6 +54,11 @@ impl Summary {
             for i in 0..self.player_summaries[0].drawn_resources[self.player_summaries[0].drawn_resources.len() - 1].1.len() {
                 headers.push(format!("drawn_resource_{}", i))
             }
+
+            headers.push("remaining_roads".to_string());
+            headers.push("remaining_settlements".to_string());
+            headers.push("remaining_cities".to_string());
+
             wtr.write_record(&headers)?;
         }
             
@@ -70,6 +75,9 @@ impl Summary {
             for dr in &ps.drawn_resources[ps.drawn_resources.len() - 1].1 {
                 row.push(dr.to_string());
             }
+            for rp in &ps.remaining_pieces {
+                row.push(rp.to_string());
+            }
             wtr.write_record(&row)?;
         }
         wtr.flush()?;
@@ -236,7 +244,9 @@ fn write_details(&self, file_path: &String) -> Result<(), Box<dyn Error>> {
                 row.push(match action {
                     Action::SetUpMove(_, _) => "SetUpMove",
                     Action::Robber(_, _, _) => "Robber",
+                    Action::Pirate(_, _, _) => "Pirate",
                     Action::DiscardCards(_) => "DiscardCards",
+                    Action::ChooseGold(_) => "ChooseGold",
                     Action::NoDiscard => "NoDiscard",
                     Action::NoCardPlay => "NoCardPlay",
                     Action::CardPlay(_) => "CardPlay",
@@ -247,8 +257,16 @@ fn write_details(&self, file_path: &String) -> Result<(), Box<dyn Error>> {
                     Action::TradeResponse(_, _) => "TradeResponse",
                     Action::NoBuying => "NoBuying",
                     Action::BuildRoad(_, _) => "BuildRoad",
+                    Action::BuildShip(_, _) => "BuildShip",
+                    Action::MoveShip(_, _, _, _) => "MoveShip",
                     Action::BuildSettlement(_) => "BuildSettlement",
                     Action::BuildCity(_) => "BuildCity",
+                    Action::BuildKnight(_) => "BuildKnight",
+                    Action::ActivateKnight(_) => "ActivateKnight",
+                    Action::PromoteKnight(_) => "PromoteKnight",
+                    Action::BuildImprovement(_) => "BuildImprovement",
+                    Action::PlayProgressCard(_) => "PlayProgressCard",
+                    Action::CommodityTrade(_, _) => "CommodityTrade",
                     Action::BuyDevCard => "BuyDevCard",
                     Action::FinishRound => "FinishRound",
                     Action::Save => "Save",
@@ -258,7 +276,9 @@ fn write_details(&self, file_path: &String) -> Result<(), Box<dyn Error>> {
                 row.push(match action {
                     Action::SetUpMove(a, b) => format!("{}, {}", a, b),
                     Action::Robber(a, b, c) => format!("{}, {}, {:?}", a, b, c),
+                    Action::Pirate(a, b, c) => format!("{}, {}, {:?}", a, b, c),
                     Action::DiscardCards(cards) => format!("{:?}", cards),
+                    Action::ChooseGold(cards) => format!("{:?}", cards),
                     Action::NoDiscard => String::new(),
                     Action::NoCardPlay => String::new(),
                     Action::CardPlay(card) => format!("{:?}", card),
@@ -269,8 +289,16 @@ fn write_details(&self, file_path: &String) -> Result<(), Box<dyn Error>> {
                     Action::TradeResponse(a, b) => format!("{}, {}", a, b),
                     Action::NoBuying => String::new(),
                     Action::BuildRoad(a, b) => format!("{}, {}", a, b),
+                    Action::BuildShip(a, b) => format!("{}, {}", a, b),
+                    Action::MoveShip(a, b, c, d) => format!("{}, {}, {}, {}", a, b, c, d),
                     Action::BuildSettlement(a) => a.to_string(),
                     Action::BuildCity(a) => a.to_string(),
+                    Action::BuildKnight(a) => a.to_string(),
+                    Action::ActivateKnight(a) => a.to_string(),
+                    Action::PromoteKnight(a) => a.to_string(),
+                    Action::BuildImprovement(a) => a.to_string(),
+                    Action::PlayProgressCard(card) => format!("{:?}", card),
+                    Action::CommodityTrade(a, b) => format!("{}, {}", a, b),
                     Action::BuyDevCard => String::new(),
                     Action::FinishRound => String::new(),
                     Action::Save => String::new(),
@@ -0,0 +1,499 @@
+use std::{collections::BTreeMap, error::Error, fs::{self, File}, path::Path};
+
+use csv::ReaderBuilder;
+use serde::{Deserialize, Serialize};
+
+use crate::backend::{round::{bank::RESOURCE_NAMES, cards::DEV_CARD_NAMES, pieces::BUILDING_NAMES}, setup::{city::City, game_parameters::{get_topology, GameParameters}, harbor::Harbor, road::Road, settlement::Settlement, shape::{check_layout, get_layout_rings, get_n_tile_rings, TileShape}}};
+
+use super::{read_parameters::read_named_matrix_str, template_registry::TemplateRegistry};
+
+
+// templates without budgets are played by this many players
+pub const DEFAULT_N_PLAYERS: u32 = 4;
+
+// a whole template in one JSON file (data/templates/<name>.json). Only the tiles and harbors are required
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Template {
+    // shown when listing the templates
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    pub tiles: Vec<TemplateTile>,
+    pub harbors: Vec<TemplateHarbor>,
+
+    // one row of resources (B, G, L, O, W) per player. Also sets the number of players
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub budgets: Option<Vec<Vec<u32>>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource_supply: Option<Vec<u32>>,
+    // building -> resource -> number of cards, e.g. {"road": {"B": 1, "L": 1}}. Buildings left out keep their default costs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub costs: Option<BTreeMap<String, BTreeMap<String, u32>>>,
+    // card type -> number of cards in the deck
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dev_cards: Option<BTreeMap<String, u32>>,
+
+    // pieces and cards already in play (settled templates)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub roads: Option<Vec<TemplateRoad>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub settlements: Option<Vec<TemplateBuilding>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cities: Option<Vec<TemplateBuilding>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub drawn_dev_cards: Option<Vec<Vec<u32>>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_dev_cards: Option<Vec<Vec<u32>>>,
+}
+
+// resource: 0-4 land resources, 5 desert, 6 sea, 7 gold. rng: number token (0 or 13: never produces).
+// axial coordinates are optional, but either all tiles or none have them
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TemplateTile {
+    pub resource: Option<u32>,
+    pub rng: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub q: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub r: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub s: Option<i32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TemplateHarbor {
+    pub first_node: u32,
+    pub second_node: u32,
+    pub harbor_type: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TemplateRoad {
+    pub player: u32,
+    pub nodes: (u32, u32),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TemplateBuilding {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub node_id: u32,
+    pub player_id: u32,
+}
+
+
+impl Template {
+    // a template by name or path, resolved along the default search path (see TemplateRegistry)
+    pub fn load(template: &str) -> Result<Self, Box<dyn Error>> {
+        TemplateRegistry::default().resolve(template)
+    }
+
+    pub fn read_json(file_path: &str) -> Result<Self, Box<dyn Error>> {
+        let json = fs::read_to_string(file_path).map_err(|e| format!("{}: {}", file_path, e))?;
+
+        Self::from_json_str(&json).map_err(|e| format!("{}: {}", file_path, e).into())
+    }
+
+    pub fn from_json_str(json: &str) -> Result<Self, Box<dyn Error>> {
+        let template: Template = serde_json::from_str(json)?;
+        template.validate()?;
+
+        Ok(template)
+    }
+
+    pub fn write_json(&self, file_path: &str) -> Result<(), Box<dyn Error>> {
+        let file = File::create(file_path)?;
+        serde_json::to_writer_pretty(file, self)?;
+
+        Ok(())
+    }
+
+    // the template directories of loose CSV files. Optional files may be missing, but present files have to be valid
+    pub fn import_csv(dir: &str) -> Result<Self, Box<dyn Error>> {
+        let read_file = |file: &str| fs::read_to_string(Path::new(dir).join(file)).ok();
+
+        Self::import_csv_files(dir, &read_file)
+    }
+
+    // the CSV files of a template as returned by read_file (None: no such file), e.g. from a directory or embedded in the binary
+    pub fn import_csv_files(dir: &str, read_file: &dyn Fn(&str) -> Option<String>) -> Result<Self, Box<dyn Error>> {
+        let path = |file: &str| dir.to_owned() + "/" + file;
+
+        let required = |file: &str| -> Result<String, Box<dyn Error>> {
+            read_file(file).ok_or_else(|| format!("{}: file missing.", path(file)).into())
+        };
+
+        let resources = read_optional_column(&required("tile_resource.csv")?, &path("tile_resource.csv"))?;
+        let rngs = read_optional_column(&required("init_tile_rng.csv")?, &path("init_tile_rng.csv"))?;
+
+        if resources.len() != rngs.len() {
+            return Err(format!("{}: {} tiles, but {} number tokens in init_tile_rng.csv.", dir, resources.len(), rngs.len()).into())
+        }
+
+        let mut tiles: Vec<TemplateTile> = resources.into_iter()
+                                            .zip(rngs)
+                                            .map(|(resource, rng)| TemplateTile { resource, rng, ..Default::default() })
+                                            .collect();
+
+        if let Some(content) = read_file("layout.csv") {
+            let layout: Vec<LayoutRow> = deserialize_rows(&content, &path("layout.csv"))?;
+
+            if layout.len() != tiles.len() {
+                return Err(format!("{}: {} coordinates for {} tiles.", path("layout.csv"), layout.len(), tiles.len()).into())
+            }
+
+            for (tile, row) in tiles.iter_mut().zip(layout) {
+                tile.q = Some(row.q);
+                tile.r = Some(row.r);
+                tile.s = row.s;
+            }
+        }
+
+        let harbors = deserialize_rows(&required("harbors.csv")?, &path("harbors.csv"))?;
+
+        let read_matrix = |file: &str| -> Result<Option<Vec<Vec<u32>>>, Box<dyn Error>> {
+            match read_file(file) {
+                Some(content) => Ok(Some(read_matrix_rows(&content, &path(file))?)),
+                None => Ok(None),
+            }
+        };
+
+        let costs = match read_file("costs.csv") {
+            Some(content) => {
+                let matrix = read_named_matrix_str(&content, &path("costs.csv"), &BUILDING_NAMES, &RESOURCE_NAMES)?;
+                Some(BUILDING_NAMES.iter()
+                        .zip(matrix)
+                        .map(|(building, row)| (building.to_string(), RESOURCE_NAMES.iter().map(|r| r.to_string()).zip(row).collect()))
+                        .collect())
+            },
+            None => None,
+        };
+
+        let dev_cards = match read_file("dev_cards.csv") {
+            Some(content) => {
+                let matrix = read_named_matrix_str(&content, &path("dev_cards.csv"), &DEV_CARD_NAMES, &["count"])?;
+                Some(DEV_CARD_NAMES.iter().map(|card| card.to_string()).zip(matrix.iter().map(|row| row[0])).collect())
+            },
+            None => None,
+        };
+
+        let roads = match read_file("init_roads.csv") {
+            Some(content) => {
+                let roads: Vec<Road> = deserialize_rows(&content, &path("init_roads.csv"))?;
+                Some(roads.into_iter().map(|road| TemplateRoad { player: road.player, nodes: road.nodes }).collect())
+            },
+            None => None,
+        };
+
+        let read_buildings = |file: &str| -> Result<Option<Vec<TemplateBuilding>>, Box<dyn Error>> {
+            match read_file(file) {
+                Some(content) => Ok(Some(deserialize_rows(&content, &path(file))?)),
+                None => Ok(None),
+            }
+        };
+
+        let template = Template {
+            description: None,
+            tiles,
+            harbors,
+            budgets: read_matrix("init_budgets.csv")?,
+            resource_supply: read_matrix("resource_supply.csv")?.and_then(|supply| supply.first().cloned()),
+            costs,
+            dev_cards,
+            roads,
+            settlements: read_buildings("init_settlements.csv")?,
+            cities: read_buildings("init_cities.csv")?,
+            drawn_dev_cards: read_matrix("init_drawn_dev_cards.csv")?,
+            public_dev_cards: read_matrix("init_public_dev_cards.csv")?,
+        };
+
+        template.validate().map_err(|e| format!("{}: {}", dir, e))?;
+
+        Ok(template)
+    }
+
+    // checks every value against the board it describes. Errors name the offending entry and field, e.g. tiles[3].rng
+    pub fn validate(&self) -> Result<(), Box<dyn Error>> {
+        let n_resources = RESOURCE_NAMES.len() as u32;
+
+        if self.tiles.is_empty() {
+            return Err("tiles: a template needs at least one tile.".into())
+        }
+
+        for (i_tile, tile) in self.tiles.iter().enumerate() {
+            if let Some(resource) = tile.resource {
+                if resource > n_resources + 2 {
+                    return Err(format!("tiles[{}].resource: unknown terrain {} (0-4 resources, 5 desert, 6 sea, 7 gold).", i_tile, resource).into())
+                }
+            }
+
+            if let Some(rng) = tile.rng {
+                if rng == 1 || rng == 7 || rng > 13 {
+                    return Err(format!("tiles[{}].rng: {} is not a number token (2-12 without 7, or 0 or 13 for none).", i_tile, rng).into())
+                }
+            }
+        }
+
+        let tile_shape = self.get_tile_shape()?;
+        let n_tile_rings = match &tile_shape {
+            TileShape::Layout(layout) => get_layout_rings(layout),
+            TileShape::Hexagon => get_n_tile_rings(self.tiles.len() as u32, &TileShape::Hexagon)
+                                    .ok_or(format!("tiles: {} tiles do not fit a hexagonal board (give coordinates q and r).", self.tiles.len()))?,
+        };
+
+        let (node_adjacency, _, _, _) = get_topology(n_tile_rings, &tile_shape);
+        let n_nodes = node_adjacency.len() as u32;
+
+        let check_node = |field: String, node: u32| -> Result<(), Box<dyn Error>> {
+            match node < n_nodes {
+                true => Ok(()),
+                false => Err(format!("{}: node {} is not on the board ({} nodes).", field, node, n_nodes).into()),
+            }
+        };
+
+        let check_edge = |field: String, nodes: (u32, u32)| -> Result<(), Box<dyn Error>> {
+            check_node(field.clone(), nodes.0)?;
+            check_node(field.clone(), nodes.1)?;
+
+            match node_adjacency[nodes.0 as usize].contains(&Some(nodes.1)) {
+                true => Ok(()),
+                false => Err(format!("{}: nodes {} and {} are not adjacent.", field, nodes.0, nodes.1).into()),
+            }
+        };
+
+        for (i_harbor, harbor) in self.harbors.iter().enumerate() {
+            check_edge(format!("harbors[{}]", i_harbor), (harbor.first_node, harbor.second_node))?;
+
+            if harbor.harbor_type > n_resources {
+                return Err(format!("harbors[{}].harbor_type: unknown harbor {} (0-4 resources, 5 generic).", i_harbor, harbor.harbor_type).into())
+            }
+        }
+
+        let n_players = self.get_n_players();
+        if !(2..=6).contains(&n_players) {
+            return Err(format!("budgets: {} players, but games need 2 to 6 players.", n_players).into())
+        }
+
+        let check_matrix = |field: &str, matrix: &Option<Vec<Vec<u32>>>, n_columns: usize| -> Result<(), Box<dyn Error>> {
+            if let Some(rows) = matrix {
+                if rows.len() != n_players as usize {
+                    return Err(format!("{}: {} rows for {} players.", field, rows.len(), n_players).into())
+                }
+
+                for (i_row, row) in rows.iter().enumerate() {
+                    if row.len() != n_columns {
+                        return Err(format!("{}[{}]: expected {} values, found {}.", field, i_row, n_columns, row.len()).into())
+                    }
+                }
+            }
+            Ok(())
+        };
+
+        check_matrix("budgets", &self.budgets, n_resources as usize)?;
+        check_matrix("drawn_dev_cards", &self.drawn_dev_cards, DEV_CARD_NAMES.len())?;
+        check_matrix("public_dev_cards", &self.public_dev_cards, DEV_CARD_NAMES.len())?;
+
+        if let Some(supply) = &self.resource_supply {
+            if supply.len() != n_resources as usize {
+                return Err(format!("resource_supply: expected {} resources, found {}.", n_resources, supply.len()).into())
+            }
+        }
+
+        if let Some(costs) = &self.costs {
+            for (building, resource_costs) in costs {
+                if !BUILDING_NAMES.contains(&building.as_str()) {
+                    return Err(format!("costs.{}: unknown building (expected one of {:?}).", building, BUILDING_NAMES).into())
+                }
+
+                for resource in resource_costs.keys() {
+                    if !RESOURCE_NAMES.contains(&resource.as_str()) {
+                        return Err(format!("costs.{}.{}: unknown resource (expected one of {:?}).", building, resource, RESOURCE_NAMES).into())
+                    }
+                }
+            }
+        }
+
+        if let Some(dev_cards) = &self.dev_cards {
+            for card in dev_cards.keys() {
+                if !DEV_CARD_NAMES.contains(&card.as_str()) {
+                    return Err(format!("dev_cards.{}: unknown card type (expected one of {:?}).", card, DEV_CARD_NAMES).into())
+                }
+            }
+        }
+
+        let check_player = |field: String, player: u32| -> Result<(), Box<dyn Error>> {
+            match player < n_players {
+                true => Ok(()),
+                false => Err(format!("{}: player {} does not exist ({} players).", field, player, n_players).into()),
+            }
+        };
+
+        for (i_road, road) in self.roads.iter().flatten().enumerate() {
+            check_player(format!("roads[{}].player", i_road), road.player)?;
+            check_edge(format!("roads[{}].nodes", i_road), road.nodes)?;
+        }
+
+        for (field, buildings) in [("settlements", &self.settlements), ("cities", &self.cities)] {
+            for (i_building, building) in buildings.iter().flatten().enumerate() {
+                check_player(format!("{}[{}].player_id", field, i_building), building.player_id)?;
+                check_node(format!("{}[{}].node_id", field, i_building), building.node_id)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn get_n_players(&self) -> u32 {
+        match &self.budgets {
+            Some(budgets) => budgets.len() as u32,
+            None => DEFAULT_N_PLAYERS,
+        }
+    }
+
+    // tiles with coordinates form a layout, tiles without a hexagon
+    fn get_tile_shape(&self) -> Result<TileShape, Box<dyn Error>> {
+        let n_with_coordinates = self.tiles.iter().filter(|tile| tile.q.is_some() && tile.r.is_some()).count();
+
+        if n_with_coordinates == 0 {
+            return Ok(TileShape::Hexagon)
+        }
+
+        if let Some(i_tile) = self.tiles.iter().position(|tile| tile.q.is_none() || tile.r.is_none()) {
+            return Err(format!("tiles[{}]: either all tiles or none have the coordinates q and r.", i_tile).into())
+        }
+
+        let raw_layout: Vec<(i32, i32, Option<i32>)> = self.tiles.iter().map(|tile| (tile.q.unwrap(), tile.r.unwrap(), tile.s)).collect();
+        let layout = check_layout(&raw_layout, self.tiles.len()).map_err(|e| format!("tiles: {}", e))?;
+
+        Ok(TileShape::Layout(layout))
+    }
+
+    // the game parameters of the template. Pieces, budgets and cards in play are only set up for settled games
+    pub fn to_parameters(self, title: Option<String>, settled: bool) -> Result<GameParameters, Box<dyn Error>> {
+        let tile_shape = self.get_tile_shape()?;
+        let n_players = self.get_n_players();
+
+        let default: GameParameters = Default::default();
+
+        let parameters = GameParameters {
+            title,
+            init_harbors: Some(self.harbors.iter().map(|harbor| Harbor::new((harbor.first_node, harbor.second_node, harbor.harbor_type))).collect()),
+            v_tile_resources: self.tiles.iter().map(|tile| tile.resource).collect(),
+            init_tile_rng: self.tiles.iter().map(|tile| tile.rng).collect(),
+            ..default
+        };
+
+        let parameters = match tile_shape {
+            TileShape::Layout(layout) => parameters.with_layout(layout),
+            TileShape::Hexagon => {
+                let n_tile_rings = get_n_tile_rings(self.tiles.len() as u32, &TileShape::Hexagon).ok_or("The number of tiles does not fit a hexagonal board.")?;
+                parameters.with_tile_rings(n_tile_rings)
+            },
+        };
+
+        let mut parameters = parameters.with_n_players(n_players);
+
+        if let Some(costs) = &self.costs {
+            for (building, resource_costs) in costs {
+                let i_building = BUILDING_NAMES.iter().position(|name| name == building).ok_or("Unknown building.")?;
+
+                parameters.building_costs[i_building] = RESOURCE_NAMES.iter()
+                                                            .map(|resource| resource_costs.get(*resource).copied().unwrap_or(0))
+                                                            .collect();
+            }
+        }
+
+        if let Some(dev_cards) = &self.dev_cards {
+            parameters.init_undrawn_dev_cards = DEV_CARD_NAMES.iter()
+                                                    .map(|card| dev_cards.get(*card).copied().unwrap_or(0))
+                                                    .collect();
+        }
+
+        if let Some(supply) = self.resource_supply {
+            parameters.resource_supply = supply;
+        }
+
+        if settled {
+            parameters.init_roads = self.roads.map(|roads| roads.into_iter().map(|road| Road { player: road.player, nodes: road.nodes }).collect());
+            parameters.init_settlements = self.settlements.map(|settlements| settlements.into_iter()
+                                                                .map(|s| Settlement { name: s.name, node_id: s.node_id, player_id: s.player_id })
+                                                                .collect());
+            parameters.init_cities = self.cities.map(|cities| cities.into_iter()
+                                                    .map(|c| City { name: c.name, node_id: c.node_id, player_id: c.player_id })
+                                                    .collect());
+            parameters.init_budgets = self.budgets;
+            parameters.init_drawn_dev_cards = self.drawn_dev_cards;
+            parameters.init_public_dev_cards = self.public_dev_cards;
+        }
+
+        Ok(parameters)
+    }
+}
+
+
+// axial coordinates of a tile. The third cube coordinate is optional
+#[derive(Debug, Deserialize)]
+struct LayoutRow {
+    q: i32,
+    r: i32,
+    s: Option<i32>,
+}
+
+// a single column with a header. Empty and NaN cells are None, anything else has to be a number
+fn read_optional_column(content: &str, file_path: &str) -> Result<Vec<Option<u32>>, Box<dyn Error>> {
+    let mut rdr = ReaderBuilder::new().has_headers(true).from_reader(content.as_bytes());
+
+    let mut column = vec![];
+    for (i_row, record) in rdr.records().enumerate() {
+        let record = record.map_err(|e| format!("{}: {}", file_path, e))?;
+        let value = record.get(0).unwrap_or("").trim();
+
+        match value {
+            "" | "NaN" => column.push(None),
+            _ => column.push(Some(value.parse::<u32>().map_err(|_| format!("{}: row {}: {} is not a number.", file_path, i_row + 1, value))?)),
+        }
+    }
+
+    Ok(column)
+}
+
+// a matrix of numbers below a header row
+fn read_matrix_rows(content: &str, file_path: &str) -> Result<Vec<Vec<u32>>, Box<dyn Error>> {
+    let mut rdr = ReaderBuilder::new().has_headers(true).from_reader(content.as_bytes());
+
+    let headers = rdr.headers()?.clone();
+
+    let mut matrix = vec![];
+    for (i_row, record) in rdr.records().enumerate() {
+        let record = record.map_err(|e| format!("{}: {}", file_path, e))?;
+
+        let row = record.iter()
+                    .zip(headers.iter())
+                    .map(|(value, header)| value.trim().parse::<u32>().map_err(|_| format!("{}: row {}, column {}: {} is not a number.", file_path, i_row + 1, header, value)))
+                    .collect::<Result<Vec<u32>, String>>()?;
+
+        matrix.push(row);
+    }
+
+    Ok(matrix)
+}
+
+// rows deserialized by their headers. Errors carry the record and field (csv crate)
+fn deserialize_rows<T>(content: &str, file_path: &str) -> Result<Vec<T>, Box<dyn Error>>
+where
+    T: for<'a> Deserialize<'a>,
+{
+    let mut rdr = ReaderBuilder::new().has_headers(true).from_reader(content.as_bytes());
+
+    let mut rows = vec![];
+    for result in rdr.deserialize::<T>() {
+        rows.push(result.map_err(|e| format!("{}: {}", file_path, e))?);
+    }
+
+    Ok(rows)
+}
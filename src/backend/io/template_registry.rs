@@ -0,0 +1,210 @@
+use std::{env, error::Error, fs, path::{Path, PathBuf}};
+
+use crate::backend::setup::shape::{get_n_tile_rings, TileShape};
+
+use super::{assets::get_override_dir, template::Template};
+
+
+// the files of a template directory, compiled into the binary
+macro_rules! embed_template {
+    ($name:literal, [$($file:literal),* $(,)?]) => {
+        ($name, &[$(($file, include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/data/templates/", $name, "/", $file)))),*])
+    };
+}
+
+// built-in templates, available from any working directory
+pub const EMBEDDED_TEMPLATES: [(&str, &[(&str, &str)]); 6] = [
+    embed_template!("default", ["tile_resource.csv", "init_tile_rng.csv", "harbors.csv", "init_budgets.csv", "costs.csv", "dev_cards.csv"]),
+    embed_template!("default-5-players", ["tile_resource.csv", "init_tile_rng.csv", "harbors.csv", "init_budgets.csv"]),
+    embed_template!("default-6-players", ["tile_resource.csv", "init_tile_rng.csv", "harbors.csv", "init_budgets.csv"]),
+    embed_template!("beginner-map", ["tile_resource.csv", "init_tile_rng.csv", "harbors.csv", "init_budgets.csv", "init_roads.csv", "init_settlements.csv", "init_cities.csv"]),
+    embed_template!("seafarers-islands", ["tile_resource.csv", "init_tile_rng.csv", "harbors.csv", "init_budgets.csv"]),
+    embed_template!("rectangle", ["tile_resource.csv", "init_tile_rng.csv", "harbors.csv", "init_budgets.csv", "layout.csv"]),
+];
+
+// directories of templates (separated like PATH) searched before the default directories
+pub const TEMPLATE_PATH_VARIABLE: &str = "CATAN_TEMPLATE_PATH";
+
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemplateSource {
+    // holds templates as <name>.json or as directories <name>/ of CSV files
+    Dir(PathBuf),
+    Embedded,
+}
+
+// what is known about a template without setting up a game
+#[derive(Debug, Clone)]
+pub struct TemplateInfo {
+    pub name: String,
+    pub source: TemplateSource,
+    pub description: Option<String>,
+    pub n_tiles: u32,
+    pub n_players: u32,
+    pub n_harbors: u32,
+    // tiles at explicit coordinates instead of a hexagon with n rings
+    pub n_tile_rings: Option<u32>,
+    // pieces already placed
+    pub settled: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct TemplateRegistry {
+    // earlier sources shadow templates of the same name in later ones
+    pub search_path: Vec<TemplateSource>,
+}
+
+
+impl Default for TemplateRegistry {
+    // $CATAN_TEMPLATE_PATH, $CATAN_ASSET_DIR/templates, the templates of the working directory, the user's templates and the built-in templates
+    fn default() -> Self {
+        let mut search_path: Vec<TemplateSource> = match env::var_os(TEMPLATE_PATH_VARIABLE) {
+            Some(paths) => env::split_paths(&paths).map(TemplateSource::Dir).collect(),
+            None => vec![],
+        };
+
+        if let Some(asset_dir) = get_override_dir() {
+            search_path.push(TemplateSource::Dir(asset_dir.join("templates")));
+        }
+
+        search_path.push(TemplateSource::Dir(PathBuf::from("data/templates")));
+
+        if let Some(user_dir) = get_user_template_dir() {
+            search_path.push(TemplateSource::Dir(user_dir));
+        }
+
+        search_path.push(TemplateSource::Embedded);
+
+        TemplateRegistry { search_path }
+    }
+}
+
+impl TemplateRegistry {
+    pub fn new(search_path: Vec<TemplateSource>) -> Self {
+        TemplateRegistry { search_path }
+    }
+
+    // an explicit path (a .json file or a directory of CSV files) or the name of a template on the search path
+    pub fn resolve(&self, template: &str) -> Result<Template, Box<dyn Error>> {
+        let path = Path::new(template);
+
+        if path.is_file() {
+            return Template::read_json(template)
+        }
+
+        if path.is_dir() && path.join("tile_resource.csv").exists() {
+            return Template::import_csv(template)
+        }
+
+        for source in &self.search_path {
+            if let Some(result) = load_from_source(source, template) {
+                return result
+            }
+        }
+
+        Err(format!("Template {} not found in {:?}.", template, self.search_path).into())
+    }
+
+    // every template on the search path (in order, without shadowed duplicates). Invalid templates are skipped
+    pub fn list(&self) -> Vec<TemplateInfo> {
+        let mut infos: Vec<TemplateInfo> = vec![];
+
+        for source in &self.search_path {
+            for name in get_template_names(source) {
+                if infos.iter().any(|info| info.name == name) {
+                    continue
+                }
+
+                if let Some(Ok(template)) = load_from_source(source, &name) {
+                    infos.push(TemplateInfo::new(name, source.clone(), &template));
+                }
+            }
+        }
+
+        infos
+    }
+}
+
+impl TemplateInfo {
+    fn new(name: String, source: TemplateSource, template: &Template) -> Self {
+        let n_tiles = template.tiles.len() as u32;
+        let has_layout = template.tiles.iter().any(|tile| tile.q.is_some());
+
+        TemplateInfo {
+            name,
+            source,
+            description: template.description.clone(),
+            n_tiles,
+            n_players: template.get_n_players(),
+            n_harbors: template.harbors.len() as u32,
+            n_tile_rings: if has_layout { None } else { get_n_tile_rings(n_tiles, &TileShape::Hexagon) },
+            settled: template.roads.is_some() || template.settlements.is_some(),
+        }
+    }
+}
+
+
+// a built-in template, whatever the search path
+pub fn load_embedded(name: &str) -> Result<Template, Box<dyn Error>> {
+    load_from_source(&TemplateSource::Embedded, name).unwrap_or_else(|| Err(format!("No built-in template {}.", name).into()))
+}
+
+// None if the source has no template of that name
+fn load_from_source(source: &TemplateSource, name: &str) -> Option<Result<Template, Box<dyn Error>>> {
+    match source {
+        TemplateSource::Dir(dir) => {
+            let json_path = dir.join(name.to_owned() + ".json");
+            let csv_dir = dir.join(name);
+
+            if json_path.is_file() {
+                Some(Template::read_json(&json_path.to_string_lossy()))
+            } else if csv_dir.join("tile_resource.csv").is_file() {
+                Some(Template::import_csv(&csv_dir.to_string_lossy()))
+            } else {
+                None
+            }
+        },
+        TemplateSource::Embedded => {
+            let (_, files) = EMBEDDED_TEMPLATES.iter().find(|(embedded_name, _)| *embedded_name == name)?;
+
+            let read_file = |file: &str| files.iter().find(|(embedded_file, _)| *embedded_file == file).map(|(_, content)| content.to_string());
+
+            Some(Template::import_csv_files(&format!("<built-in>/{}", name), &read_file))
+        },
+    }
+}
+
+fn get_template_names(source: &TemplateSource) -> Vec<String> {
+    match source {
+        TemplateSource::Dir(dir) => {
+            let mut names: Vec<String> = match fs::read_dir(dir) {
+                Ok(entries) => entries.filter_map(|entry| entry.ok())
+                                    .map(|entry| entry.path())
+                                    .filter_map(|path| {
+                                        if path.is_dir() {
+                                            path.file_name().map(|name| name.to_string_lossy().to_string())
+                                        } else if path.extension().map_or(false, |extension| extension == "json") {
+                                            path.file_stem().map(|name| name.to_string_lossy().to_string())
+                                        } else {
+                                            None
+                                        }
+                                    })
+                                    .collect(),
+                Err(_) => vec![],
+            };
+
+            names.sort();
+            names.dedup();
+            names
+        },
+        TemplateSource::Embedded => EMBEDDED_TEMPLATES.iter().map(|(name, _)| name.to_string()).collect(),
+    }
+}
+
+// $XDG_DATA_HOME/catan-cli/templates, or ~/.local/share/catan-cli/templates
+fn get_user_template_dir() -> Option<PathBuf> {
+    match env::var_os("XDG_DATA_HOME") {
+        Some(data_home) => Some(PathBuf::from(data_home).join("catan-cli/templates")),
+        None => env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share/catan-cli/templates")),
+    }
+}
@@ -0,0 +1,227 @@
+use std::{error::Error, fs::{self, File}, path::PathBuf};
+
+use csv::{ReaderBuilder, Writer};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{monte_carlo::run_games, setup::{game_parameters::GameParameters, player::{Player, PlayerType}}};
+
+
+#[derive(Debug, Clone)]
+pub struct LeagueEntrant {
+    pub name: String,
+    pub player_type: PlayerType,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Rating {
+    pub name: String,
+    pub elo: f64,
+    pub n_games: u32,
+    pub n_wins: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct League {
+    pub title: String,
+    pub entrants: Vec<LeagueEntrant>,
+    pub ratings: Vec<Rating>,
+    pub init_elo: f64,
+    pub k_factor: f64,
+    // where the ratings are persisted, as <title>.csv
+    pub output_dir: PathBuf,
+}
+
+impl League {
+    pub fn new(title: String, entrants: Vec<LeagueEntrant>, output_dir: PathBuf) -> Self {
+
+        let init_elo = 1500.0;
+
+        let ratings = entrants.iter()
+                        .map(|entrant| Rating {
+                            name: entrant.name.clone(),
+                            elo: init_elo,
+                            n_games: 0,
+                            n_wins: 0,
+                        })
+                        .collect();
+
+        Self {
+            title,
+            entrants,
+            ratings,
+            init_elo,
+            k_factor: 16.0,
+            output_dir,
+        }
+    }
+
+    pub fn ratings_path(&self) -> PathBuf {
+        self.output_dir.join(self.title.clone() + ".csv")
+    }
+
+    // overwrite the ratings of all entrants which already have a persisted rating
+    pub fn load_ratings(&mut self) -> Result<(), Box<dyn Error>> {
+
+        let file_path = self.ratings_path();
+
+        if !file_path.exists() {
+            return Ok(())
+        }
+
+        let file = File::open(file_path)?;
+        let mut rdr = ReaderBuilder::new().has_headers(true).from_reader(file);
+
+        for result in rdr.deserialize::<Rating>() {
+            let saved_rating = result?;
+
+            for rating in self.ratings.iter_mut() {
+                if rating.name == saved_rating.name {
+                    *rating = saved_rating.clone();
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn save_ratings(&self) -> Result<(), Box<dyn Error>> {
+
+        fs::create_dir_all(&self.output_dir)?;
+
+        let file = File::create(self.ratings_path())?;
+        let mut wtr = Writer::from_writer(file);
+
+        for rating in &self.ratings {
+            wtr.serialize(rating)?;
+        }
+
+        wtr.flush()?;
+
+        Ok(())
+    }
+
+    // play every combination of entrants in every rotated seating, update the ratings after each game and persist them
+    pub fn play_round_robin(&mut self, game_parameters: &GameParameters, n_games_per_seating: u32) -> Result<(), Box<dyn Error>> {
+
+        let n_players = game_parameters.n_players as usize;
+
+        if self.entrants.len() < n_players {
+            return Err("A league needs at least as many entrants as there are seats.".into())
+        }
+
+        self.load_ratings()?;
+
+        let schedule = get_round_robin_schedule(self.entrants.len(), n_players);
+
+        let mut i_game = 0_u64;
+        for combination in schedule.chunks(n_players) {
+
+            let mut v_parameters: Vec<GameParameters> = vec![];
+            let mut v_seatings: Vec<Vec<usize>> = vec![];
+
+            for seating in combination {
+                for _ in 0..n_games_per_seating {
+                    let mut parameters = game_parameters.clone();
+
+                    parameters.id = Uuid::new_v4().to_string();
+                    parameters.title = Some(self.title.clone());
+
+                    parameters.v_players = seating.iter()
+                                            .enumerate()
+                                            .map(|(seat, &i_entrant)| Player::from_type(seat as u32, self.entrants[i_entrant].player_type.clone()))
+                                            .collect();
+
+                    parameters = parameters.with_game_index(i_game);
+
+                    v_parameters.push(parameters);
+                    v_seatings.push(seating.clone());
+
+                    i_game += 1;
+                }
+            }
+
+            let games = run_games(v_parameters);
+
+            // games that could not be set up keep their place, so every result is credited to its own seating
+            for (game, seating) in games.iter().zip(v_seatings.iter()) {
+                if let Some(summary) = game.as_ref().and_then(|game| game.result.as_ref()) {
+                    let old_elos: Vec<f64> = seating.iter().map(|&i_entrant| self.ratings[i_entrant].elo).collect();
+                    let new_elos = update_elo(&old_elos, &summary.final_scores, self.k_factor);
+
+                    for (seat, &i_entrant) in seating.iter().enumerate() {
+                        self.ratings[i_entrant].elo = new_elos[seat];
+                        self.ratings[i_entrant].n_games += 1;
+
+                        if summary.winner_id as usize == seat {
+                            self.ratings[i_entrant].n_wins += 1;
+                        }
+                    }
+                }
+            }
+
+            println!("League {}: {:?} games played.", self.title, i_game);
+        }
+
+        self.save_ratings()?;
+
+        Ok(())
+    }
+}
+
+
+// every combination of entrants, each in all n_players rotations of its seating (removes first-player bias)
+pub fn get_round_robin_schedule(n_entrants: usize, n_players: usize) -> Vec<Vec<usize>> {
+
+    let mut schedule: Vec<Vec<usize>> = vec![];
+
+    for combination in (0..n_entrants).combinations(n_players) {
+        for i_rotation in 0..n_players {
+            let seating: Vec<usize> = (0..n_players)
+                                        .map(|seat| combination[(seat + i_rotation) % n_players])
+                                        .collect();
+            schedule.push(seating);
+        }
+    }
+
+    schedule
+}
+
+
+// multi-player Elo: every pair of seats is scored as a separate duel based on the final standings
+pub fn update_elo(elos: &Vec<f64>, final_scores: &Vec<u32>, k_factor: f64) -> Vec<f64> {
+
+    let n_players = elos.len();
+    let mut new_elos = elos.clone();
+
+    if n_players < 2 {
+        return new_elos
+    }
+
+    for i_player in 0..n_players {
+        let mut delta = 0.0;
+
+        for j_player in 0..n_players {
+            if i_player == j_player {
+                continue
+            }
+
+            let expected = 1.0 / (1.0 + 10_f64.powf((elos[j_player] - elos[i_player]) / 400.0));
+
+            let actual = if final_scores[i_player] > final_scores[j_player] {
+                1.0
+            } else if final_scores[i_player] == final_scores[j_player] {
+                0.5
+            } else {
+                0.0
+            };
+
+            delta += actual - expected;
+        }
+
+        new_elos[i_player] += k_factor * delta / (n_players - 1) as f64;
+    }
+
+    new_elos
+}
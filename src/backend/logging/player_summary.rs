@@ -2,7 +2,7 @@ use std::{vec, collections::HashSet};
 
 use serde::{Deserialize, Serialize};
 
-use crate::backend::{round::{action::Action, outcome::Outcome}, setup::{game::Game, node_status::NodeStatus::{Citied, Settled}, player}};
+use crate::backend::{round::{action::Action, outcome::Outcome, pieces::get_remaining_pieces}, setup::{game::Game, node_status::NodeStatus::{Citied, Settled}, player}};
 
 
 #[derive(Debug, Deserialize, Clone, Serialize)]
@@ -15,6 +15,7 @@ pub struct PlayerSummary {
     pub settlements: Vec<u32>,
     pub cities: Vec<u32>,
     pub roads: Vec<(u32, u32)>,
+    pub remaining_pieces: Vec<u32>,
 }
 
 impl PlayerSummary {
@@ -27,8 +28,11 @@ impl PlayerSummary {
         let mut settlements: Vec<u32> = vec![];
         let mut cities: Vec<u32> = vec![];
         let mut roads: Vec<(u32, u32)> = vec![];
+        let mut remaining_pieces: Vec<u32> = game.parameters.max_pieces.clone();
         match &game.log[n_rounds-1].round {
             Some(round) => {
+                remaining_pieces = get_remaining_pieces(player_id as u32, &round.board, &game.parameters.max_pieces);
+
                 for node in &round.board.nodes {
                     match &node.node_status {
                         Settled(owner) => {
@@ -119,6 +123,7 @@ impl PlayerSummary {
             settlements,
             cities,
             roads,
+            remaining_pieces,
         }
     }
 }
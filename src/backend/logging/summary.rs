@@ -12,6 +12,7 @@ pub struct Summary {
     pub game_title: Option<String>,
     pub n_rounds: usize,
     pub winner_id: u32,
+    pub final_scores: Vec<u32>,
     pub duration: u128,
     pub player_summaries: Vec<PlayerSummary>,
 }
@@ -27,6 +28,8 @@ impl Summary {
                                     .map(|(index, _)| index)
                                     .unwrap() as u32;
 
+        let final_scores = game.round.board.scores.clone();
+
         let duration = game.log.iter().fold(0, |mut acc, entry| {
             let dur = match entry.duration_ms {
                 Some(d) => d,
@@ -47,6 +50,7 @@ impl Summary {
             game_title: game.parameters.clone().title,
             n_rounds,
             winner_id,
+            final_scores,
             duration,
             player_summaries,
         }
@@ -2,7 +2,7 @@ use std::{error::Error, time::Instant};
 
 use crate::{backend::setup::game::Game, frontend::board_parameters::UIBoardParameters};
 
-use super::{logging::summary::Summary, setup::game_parameters::GameParameters};
+use super::{logging::summary::Summary, setup::{game_parameters::GameParameters, hidden_state::HiddenState, map_generator::MapConstraints, rng_service::{derive_seed, RngService}}};
 
 use rayon::prelude::*;
 use uuid::Uuid;
@@ -33,9 +33,12 @@ pub fn simulate_games(n_sims: u32, game_parameters: &GameParameters, template: S
                         game.parameters.id = Uuid::new_v4().to_string();
                         game.parameters.title = Some(title.clone());
 
-                        game.parameters.dice_seed += i_game as u64;
-                        game.parameters.robber_seed += i_game as u64;
-                        game.parameters.dev_card_seed += i_game as u64;
+                        game.parameters = game.parameters.with_game_index(i_game as u64);
+
+                        // the random streams were seeded and the hidden decks shuffled when the board was set up
+                        game.round.board.rng = RngService::new(&game.parameters);
+                        game.hidden = HiddenState::new(&game.parameters, &mut game.round.board.rng);
+                        game.round.board.n_undrawn_dev_cards = game.hidden.dev_deck.len() as u32;
                         
                         println!("Simulation {:?} set up.", i_game);
 
@@ -75,4 +78,66 @@ pub fn simulate_games(n_sims: u32, game_parameters: &GameParameters, template: S
     println!("Simulations finished in {:?} seconds.", start.elapsed().as_secs());
     
     Ok(())
-}
\ No newline at end of file
+}
+
+// run a batch of fully specified games in parallel. One entry per parameter set, in order, None if the game could not be set up
+pub fn run_games(v_parameters: Vec<GameParameters>) -> Vec<Option<Game>> {
+
+    v_parameters.into_par_iter()
+        .map(|parameters| {
+
+            let mut game = match Game::new(parameters) {
+                Ok(g) => g,
+                Err(e) => {
+                    println!("{}", e);
+                    return None
+                },
+            };
+
+            match game.run() {
+                Ok(_) => (),
+                Err(e) => println!("{}", e),
+            };
+
+            Some(game)
+        })
+        .collect()
+}
+
+// like simulate_games, but every game is played on its own random map drawn from its own tile seed
+pub fn simulate_random_maps(n_sims: u32, game_parameters: &GameParameters, constraints: &MapConstraints, title: String) -> Result<(), Box<dyn Error>> {
+
+    let start = Instant::now();
+
+    let n_blocks = 10;
+    let block_size = (n_sims / n_blocks).max(1);
+
+    let mut first_sim = 0;
+    while first_sim < n_sims {
+
+        let last_sim = (first_sim + block_size).min(n_sims);
+
+        let mut v_parameters: Vec<GameParameters> = vec![];
+        for i_game in first_sim..last_sim {
+            let mut parameters = game_parameters.clone();
+
+            parameters.id = Uuid::new_v4().to_string();
+            parameters.title = Some(title.clone());
+
+            // the map is drawn from the derived tile seed as well
+            let master_seed = derive_seed(game_parameters.dice_seed, i_game as u64);
+
+            v_parameters.push(parameters.with_master_seed(master_seed).with_random_map(constraints)?);
+        }
+
+        for game in run_games(v_parameters).into_iter().flatten() {
+            game.encode_log()?;
+        }
+
+        first_sim = last_sim;
+    }
+
+    println!("Simulations finished in {:?} seconds.", start.elapsed().as_secs());
+
+    Ok(())
+}
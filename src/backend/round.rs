@@ -5,5 +5,9 @@ pub mod outcome;
 pub mod phase;
 pub mod score;
 pub mod longest_road;
+pub mod largest_army;
 pub mod legal;
-pub mod cards;
\ No newline at end of file
+pub mod cards;
+pub mod bank;
+pub mod pieces;
+pub mod cities_knights;
@@ -1,15 +1,17 @@
-use rand::{rngs::StdRng, Rng, SeedableRng};
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 
-use crate::backend::{logging::log_entry::LogEntry, setup::{city::City, game::Game, road::Road, settlement::Settlement}};
+use crate::backend::{logging::log_entry::LogEntry, setup::{city::City, game::Game, rng_service::Stream, road::Road, ruleset::Ruleset, settlement::Settlement, ship::Ship}};
 
-use super::{cards::CardType, longest_road::get_longest_road, outcome::Outcome, score::{get_public_score, get_score}};
+use super::{bank::{apply_shortage_rule, get_bank_stock}, cards::{CardType, VP_CARD}, cities_knights::{activate_knight, build_improvement, get_cities_knights_score, place_and_pay_knight, play_progress_card, promote_knight, trade_commodity, update_metropolises, ProgressCard}, largest_army::update_largest_army, longest_road::{get_longest_road, update_longest_road}, outcome::Outcome, phase::Phase, score::{get_public_score, get_score}};
 
 #[derive(Debug, Clone, Deserialize, PartialEq, Serialize)]
 pub enum Action {
    SetUpMove(u32, u32), 
    Robber(u32, u32, Option<u32>),
+   Pirate(u32, u32, Option<u32>),
    DiscardCards(Vec<u32>),
+   ChooseGold(Vec<u32>),
    NoDiscard,
    NoCardPlay,
    CardPlay(CardType), 
@@ -20,8 +22,16 @@ pub enum Action {
    TradeResponse(u32, bool),
    NoBuying,
    BuildRoad(u32, u32),
+   BuildShip(u32, u32),
+   MoveShip(u32, u32, u32, u32),
    BuildSettlement(u32),
    BuildCity(u32),
+   BuildKnight(u32),
+   ActivateKnight(u32),
+   PromoteKnight(u32),
+   BuildImprovement(u32),
+   PlayProgressCard(ProgressCard),
+   CommodityTrade(u32, u32),
    BuyDevCard,
    FinishRound, 
    Save,
@@ -49,103 +59,71 @@ impl Game {
 
         match legal_action.clone() {
             Action::SetUpMove(settlement_node, road_end) => {
-                place_settlement(self, settlement_node, building_name);
+                let i_setup_round = self.round.phase_count / self.parameters.n_players;
+
+                place_settlement(self, settlement_node, building_name.clone());
+
+                // some variants place a city in one of the setup rounds
+                if self.parameters.setup_city_round == Some(i_setup_round) {
+                    place_city(self, settlement_node, building_name);
+                }
 
                 // println!("Tile nodes: {:?}", self.parameters.node_tiles_adjacency[settlement_node as usize]);
 
                 place_road(self, settlement_node, road_end);
+
+                if self.parameters.setup_payout.pays_out(i_setup_round) {
+                    pay_out_setup(self, settlement_node);
+                }
             },
 
             Action::Robber(i_robber, i_tile, opt_victim) => {
                 // move the robber (change v_robber and the tile rng)
-                let i_orig_tile = self.round.board.v_robbers[i_robber as usize] as usize;
-                self.round.board.tiles[i_orig_tile].rng = self.round.board.tile_rng[i_orig_tile];
-                self.round.board.tiles[i_tile as usize].rng = None;
-
-                for robber_id in 0..self.round.board.v_robbers.len() {
-                    if i_robber as usize == robber_id {
-                        self.round.board.v_robbers[robber_id] = i_tile;
-                    }
-                }
-
-                
-                if let Some(i_victim) = opt_victim {
-                    // select random resource from victim
-                    let total_resources: u32 = self.round.board.budgets[i_victim as usize].iter().sum();
-
-                    if total_resources > 0 {
-                        let cum_resources: Vec<u32> = self.round.board.budgets[i_victim as usize].iter()
-                                                        .scan(0_u32, |acc, &x| {
-                                                            *acc += x;
-                                                            Some(*acc)
-                                                        })
-                                                        .collect();
-
-                        let mut rng = StdRng::seed_from_u64(self.parameters.robber_seed);
-                        let random_card = rng.gen_range(0..total_resources as i32) as u32;
-                        
-                        self.parameters.robber_seed += 1;
-
-                        // subtract resource from victim's budget
-                        let mut resource_type = 0_usize;
-                        while cum_resources[resource_type] < random_card {
-                            resource_type += 1;
-            
-                            if cum_resources[resource_type] > random_card {
-                                resource_type -= 1;
-                                break
-                            }
-                        }
-                        
-                        if self.round.board.budgets[i_victim as usize][resource_type] != 0 {
-                            self.round.board.budgets[i_victim as usize][resource_type] -= 1;
-
-                            // add resource to active player's budget
-                            self.round.board.budgets[active_player][resource_type] += 1;
-                        }
+                move_robber(self, i_robber as usize, i_tile);
+                self.round.board.robber_players[i_robber as usize] = Some(active_player as u32);
 
-                        self.round.outcome = Some(Outcome::RobberOutcome(Some(resource_type as u32) ));
-                    } else {
-                        self.round.outcome = Some(Outcome::RobberOutcome(None));
-                    }
-                    
-                }
+                steal_resource(self, opt_victim);
+            },
 
+            Action::Pirate(i_pirate, i_edge, opt_victim) => {
+                // the pirate sits on a coastal edge and does not touch the tile rng
+                self.round.board.v_pirates[i_pirate as usize] = i_edge;
 
+                steal_resource(self, opt_victim);
             },
 
             Action::DiscardCards(discarded) => {
-                // subtract discarded cards from budget
-                self.round.board.budgets[active_player] = budget.iter()
-                                                            .zip(discarded.iter())
-                                                            .map(|(b, &d)| b - d)
-                                                            .collect();
+                // discards are simultaneous: they are only applied once every player over the hand limit has chosen
+                let i_log = self.log.len() - 1;
+                self.hidden.pending_discards.push((active_player as u32, discarded, i_log));
 
-                // discard is public. Comment out to keep it private
-                self.round.board.public_budgets[active_player] = self.round.board.public_budgets[active_player].iter()
-                                                                .zip(discarded.iter())
-                                                                .map(|(b, &d)| b.checked_sub(d).unwrap_or(0))
-                                                                .collect();
-                                                                
+                if self.round.discard_queue.iter().all(|&i_player| i_player == active_player as u32) {
+                    apply_discards(self);
+                }
             },
 
             Action::NoDiscard => {
                 
             }
 
+            Action::ChooseGold(chosen) => {
+                // resources of choice for the gold fields (paid by the bank, which is checked by the legal actions)
+                for (i_resource, n_cards) in chosen.iter().enumerate() {
+                    self.round.board.budgets[active_player][i_resource] += n_cards;
+                    self.round.board.public_budgets[active_player][i_resource] += n_cards;
+                    self.round.board.total_drawn_resources[active_player][i_resource] += n_cards;
+                }
+
+                self.round.gold_owed[active_player] = 0;
+            },
+
             Action::NoCardPlay => {
             
             },
 
             Action::CardPlay(card_type) => {
                 // add card to public cards (legal action = only non-public cards may be played)
-                let card_type_id: usize = match card_type {
-                    CardType::VPCard => 0,
-                    CardType::KnightCard(_,_, _) => 1,
-                    CardType::RoadsCard(_, _, _, _) => 2,
-                    CardType::PlentyCard(_, _) =>  3,
-                    CardType::MonopolyCard(_) => 4,
-                };
+                let card_type_id = card_type.get_id();
 
                 self.round.board.public_dev_cards[active_player][card_type_id] += 1;
 
@@ -156,15 +134,25 @@ impl Game {
                     },
 
                     CardType::KnightCard(robber_id, tile_id, opt_victim) => {  // knight card
+                        self.round.board.armies[active_player] += 1;
+
                         self.round.action = Some(Action::CardPlay(CardType::KnightCard(robber_id, tile_id, opt_victim)));
+
+                        // the robber move iterates the phase to the first card phase, the card play decides the phase itself
+                        let phase = self.round.phase.clone();
                         self.take_action(Action::Robber(robber_id, tile_id, opt_victim), None);
+                        self.round.phase = phase;
 
                     },
 
                     CardType::RoadsCard(f1, s1, f2, s2) => { // road card
                         self.round.action = Some(Action::CardPlay(CardType::RoadsCard(f1,s1,f2, s2)));
                         place_road(self, f1, s1);
-                        place_road(self, f2, s2);
+
+                        // the same road twice: the player had only one road left
+                        if (f2, s2) != (f1, s1) {
+                            place_road(self, f2, s2);
+                        }
                     },
 
                     CardType::PlentyCard(first_resource, second_resource ) => { // year of plenty
@@ -279,33 +267,51 @@ impl Game {
                 place_and_pay_road(self, start_node, end_node);
             },
 
+            Action::BuildShip(start_node, end_node) => {
+                place_and_pay_ship(self, start_node, end_node);
+            },
+
+            Action::MoveShip(old_start, old_end, new_start, new_end) => {
+                for ship in self.round.board.ships.iter_mut() {
+                    if ship.player == active_player as u32 && ship.is_on(old_start, old_end) {
+                        ship.nodes = (new_start, new_end);
+                        break
+                    }
+                }
+
+                self.round.ship_moved = true;
+            },
+
             Action::BuildSettlement(node_id) => {
                 place_and_pay_settlement(self, node_id, building_name);
             },
 
             Action::BuildCity(node_id) => {
-                let new_city = City {
-                    name: building_name,
-                    node_id,
-                    player_id: active_player as u32,
-                };
+                place_and_pay_city(self, node_id, building_name);
+            },
 
-                let nodes = &self.round.board.nodes;
+            Action::BuildKnight(node_id) => {
+                place_and_pay_knight(self, node_id);
+            },
 
-                let err_msg = format!("Could not place a city at node {:?}.", node_id);
+            Action::ActivateKnight(node_id) => {
+                activate_knight(self, node_id);
+            },
 
-                self.round.board.nodes = new_city.place(nodes.clone()).expect(&err_msg);
+            Action::PromoteKnight(node_id) => {
+                promote_knight(self, node_id);
+            },
 
-                // pay for settlement
-                self.round.board.budgets[active_player] = budget.iter()
-                                        .zip(self.parameters.building_costs[2].iter())
-                                        .map(|(b, &c)| b - c)
-                                        .collect();
+            Action::BuildImprovement(i_track) => {
+                build_improvement(self, i_track);
+            },
 
-                self.round.board.public_budgets[active_player] = self.round.board.public_budgets[active_player].iter()
-                                        .zip(self.parameters.building_costs[2].iter())
-                                        .map(|(b, &c)| b.checked_sub(c).unwrap_or(0))
-                                        .collect();
+            Action::PlayProgressCard(card) => {
+                play_progress_card(self, card);
+            },
+
+            Action::CommodityTrade(i_commodity, r_demanded) => {
+                trade_commodity(self, i_commodity, r_demanded);
             },
 
             Action::BuyDevCard => {
@@ -320,35 +326,15 @@ impl Game {
                                         .map(|(b, &c)| b.checked_sub(c).unwrap_or(0))
                                         .collect();
 
-                // determine type of development card
-                let undrawn_cards = &self.round.board.undrawn_dev_cards;
-                let cum_undrawn_cards: &Vec<u32> = &undrawn_cards.iter()
-                                                    .scan(0_u32, |acc, &x| {
-                                                        *acc += x;
-                                                        Some(*acc)
-                                                    })
-                                                    .collect();
-                let total_undrawn_cards: u32 = cum_undrawn_cards[cum_undrawn_cards.len()-1];
-
-                let mut rng = StdRng::seed_from_u64(self.parameters.dev_card_seed);
-                let random_card = rng.gen_range(0..total_undrawn_cards as i32) as u32;
-
-                // increase seed
-                self.parameters.dev_card_seed += 1;
+                // draw the top card of the shuffled deck
+                let card_type = self.hidden.dev_deck.pop().expect("The development card deck is empty.") as usize;
+                self.round.board.n_undrawn_dev_cards -= 1;
+
                 self.round.card_count += 1;
-                                                    
-                let mut card_type = 0_usize;
-                while cum_undrawn_cards[card_type] < random_card {
-                    card_type += 1;
 
-                    if cum_undrawn_cards[card_type] > random_card {
-                        card_type -= 1;
-                        break
-                    }
-                }
-                
-                // add development card to "hand"
+                // move development card from the deck to the "hand"
                 self.round.board.drawn_dev_cards[active_player][card_type] += 1;
+                self.round.board.dev_card_turns[active_player][card_type].push(self.round.turn);
 
                 self.round.outcome = Some(Outcome::DrawCardOutcome(card_type as u32));
 
@@ -370,6 +356,8 @@ impl Game {
         // add action to round (unless it is a response)
         match &legal_action {
             Action::TradeResponse(_,_) => (),
+            // a discard that is not yet applied is logged as an empty discard (apply_discards writes the real one into the log)
+            Action::DiscardCards(discarded) if !self.hidden.pending_discards.is_empty() => self.round.action = Some(Action::DiscardCards(vec![0; discarded.len()])),
             _ => self.round.action = Some(legal_action.clone())
         };
 
@@ -390,12 +378,15 @@ impl Game {
     fn refresh_board(&mut self) {
 
         let roads = &self.round.board.roads;
+        let nodes = &self.round.board.nodes;
+        let ships = &self.round.board.ships;
+        let knights = &self.round.board.knights;
 
-        // update longest roads
+        // update longest roads (including ships)
         self.round.board.longest_roads = match &roads {
             Some(v_roads) => {
                 let v_longest = (0..self.parameters.n_players)
-                                    .map(|i_player| get_longest_road(i_player, v_roads)).collect();
+                                    .map(|i_player| get_longest_road(i_player, v_roads, ships, &nodes, knights)).collect();
                 Some(v_longest)
             },
             None => {
@@ -404,82 +395,67 @@ impl Game {
         };
         // println!("longest road updated.");
 
+        // update previous longest road (before the scores, which depend on it)
+        self.round.board.prev_longest_road = update_longest_road(&self.round.board.longest_roads, &self.round.board.prev_longest_road);
+
+        // update bank stock
+        self.round.board.bank_stock = get_bank_stock(&self.round.board.budgets, &self.parameters.resource_supply);
+
+        // update largest army (before the scores, which depend on it)
+        self.round.board.prev_largest_army = update_largest_army(&self.round.board.armies, &self.round.board.prev_largest_army);
+
         let nodes = &self.round.board.nodes;
         let drawn_dev_cards = &self.round.board.drawn_dev_cards;
         let public_dev_cards = &self.round.board.public_dev_cards;
-        let longest_roads = &self.round.board.longest_roads;
         let prev_longest_road = &self.round.board.prev_longest_road;
-        let armies = &self.round.board.armies;
         let prev_largest_army = &self.round.board.prev_largest_army;
 
         // update scores
         self.round.board.scores = (0..self.parameters.n_players)
-                                .map(|i_player| get_score(i_player, &nodes,  &drawn_dev_cards, &longest_roads, &prev_longest_road,
-                                &armies, &prev_largest_army))
+                                .map(|i_player| get_score(i_player, &nodes,  &drawn_dev_cards, &prev_longest_road,
+                                &prev_largest_army))
                                 .collect();
 
         self.round.board.public_scores = (0..self.parameters.n_players)
-                                .map(|i_player| get_public_score(i_player, &nodes, &public_dev_cards, &longest_roads, &prev_longest_road,
-                                &armies, &prev_largest_army))
+                                .map(|i_player| get_public_score(i_player, &nodes, &public_dev_cards, &prev_longest_road,
+                                &prev_largest_army))
                                 .collect();
-        // println!("updated score.");
-        
-        // update previous longest road (if longest road has changed)
-        match &self.round.board.longest_roads {
-            Some(longest_roads) => {
-                match longest_roads.iter().enumerate().max_by_key(|&(_, &val)| val) {
-                    Some((index, &max_value)) => { 
-                        if max_value > 4 {
-                        match &self.round.board.prev_longest_road {
-                            Some(prev_longest_road) => {
-                                if max_value != prev_longest_road.1 {
-                                    self.round.board.prev_longest_road = Some((index as u32, max_value));
-                                }
-                            },
-                            None => {
-                                self.round.board.prev_longest_road = Some((index as u32, max_value));
-                            },
-                        }
-                        }
-    
-                    }
-                    None => {
-                                    self.round.board.prev_longest_road = None;
-                                }
-                }
-            },
-            None => {
-                self.round.board.prev_longest_road = None;
-            },
+
+        // island bonus (public, as it comes with a settlement)
+        for i_player in 0..self.parameters.n_players as usize {
+            let island_vp = self.parameters.island_bonus * self.round.board.discovered_islands[i_player].len() as u32;
+
+            self.round.board.scores[i_player] += island_vp;
+            self.round.board.public_scores[i_player] += island_vp;
         }
 
-        // println!("prev longest road updated");
+        // metropolises and defenders of Catan (Cities & Knights)
+        if self.parameters.ruleset == Ruleset::CitiesAndKnights {
+            self.round.board.metropolises = update_metropolises(&self.round.board.improvements, &self.round.board.metropolises);
 
+            for i_player in 0..self.parameters.n_players {
+                let cities_knights_vp = get_cities_knights_score(i_player, &self.round.board.metropolises, &self.round.board.defender_points, &self.round.board.progress_points);
 
-        // update previous largest army (if largest army has changed)
-        if let Some((index, largest_army)) = armies.iter().enumerate().max_by_key(|&(_, &val)| val) {
-            if largest_army > &2 {
-                match &prev_largest_army {
-                    Some(pla) => {
-                        if largest_army != &pla.1 {
-                            self.round.board.prev_largest_army = Some((index as u32, *largest_army));
-                        }
-                    },
-                    None => {
-                        self.round.board.prev_largest_army = Some((index as u32, *largest_army));
-                    }
-                        ,
-                }
+                self.round.board.scores[i_player as usize] += cities_knights_vp;
+                self.round.board.public_scores[i_player as usize] += cities_knights_vp;
             }
-        } else {
-            self.round.board.prev_largest_army = None;
-        };
-
+        }
+        // println!("updated score.");
+        
         // println!("prev largest army updated");
 
 
 
     }
+
+    // victory point cards are never played, but revealed once the game has been won
+    pub fn reveal_victory_cards(&mut self) {
+        for i_player in 0..self.parameters.n_players as usize {
+            self.round.board.public_dev_cards[i_player][VP_CARD] = self.round.board.drawn_dev_cards[i_player][VP_CARD];
+        }
+
+        self.refresh_board();
+    }
 }
 
 
@@ -489,7 +465,7 @@ fn place_and_pay_road(game: &mut Game, start_node: u32, end_node: u32) {
     pay_road(game);
 }
 
-fn place_road(game: &mut Game, start_node: u32, end_node: u32) {
+pub fn place_road(game: &mut Game, start_node: u32, end_node: u32) {
     let active_player = game.round.active_player as usize;
 
     let new_road = Road{
@@ -532,6 +508,29 @@ fn pay_road(game: &mut Game) {
                         .collect();
 }
 
+fn place_and_pay_ship(game: &mut Game, start_node: u32, end_node: u32) {
+    let active_player = game.round.active_player;
+
+    game.round.board.ships.push(Ship {
+        player: active_player,
+        nodes: (start_node, end_node),
+        turn: game.round.turn,
+    });
+
+    let budget = &game.round.board.budgets[active_player as usize];
+
+    // pay for ship
+    game.round.board.budgets[active_player as usize] = budget.iter()
+                        .zip(game.parameters.building_costs[4].iter())
+                        .map(|(b, &c)| b - c)
+                        .collect();
+
+    game.round.board.public_budgets[active_player as usize] = game.round.board.public_budgets[active_player as usize].iter()
+                        .zip(game.parameters.building_costs[4].iter())
+                        .map(|(b, &c)| b.checked_sub(c).unwrap_or(0))
+                        .collect();
+}
+
 fn place_and_pay_settlement(game:&mut Game, node_id: u32, building_name: Option<String>) {
     place_settlement(game, node_id, building_name);
 
@@ -552,6 +551,30 @@ fn place_settlement(game: &mut Game, node_id: u32, building_name: Option<String>
     let err_msg = format!("Could not place a settlement at node {:?}.", node_id);
 
     game.round.board.nodes = new_settlement.place(nodes.clone()).expect(&err_msg);
+
+    discover_island(game, node_id);
+}
+
+// islands settled during the setup become home islands. Later, the first settlement of a player on any other island earns the island bonus
+fn discover_island(game: &mut Game, node_id: u32) {
+    if !game.parameters.has_sea() {
+        return
+    }
+
+    let active_player = game.round.active_player as usize;
+
+    let i_island = match game.round.board.node_islands[node_id as usize] {
+        Some(i) => i,
+        None => return,
+    };
+
+    if game.round.phase == Phase::SetUp {
+        if !game.round.board.home_islands.contains(&i_island) {
+            game.round.board.home_islands.push(i_island);
+        }
+    } else if !game.round.board.home_islands.contains(&i_island) && !game.round.board.discovered_islands[active_player].contains(&i_island) {
+        game.round.board.discovered_islands[active_player].push(i_island);
+    }
 }
 
 fn pay_settlement(game: &mut Game) {
@@ -569,4 +592,156 @@ fn pay_settlement(game: &mut Game) {
                 .zip(game.parameters.building_costs[1].iter())
                 .map(|(b, &c)| b.checked_sub(c).unwrap_or(0))
                 .collect();
-}   
\ No newline at end of file
+}   
+
+
+fn place_and_pay_city(game:&mut Game, node_id: u32, building_name: Option<String>) {
+    place_city(game, node_id, building_name);
+
+    pay_city(game);
+}
+
+fn place_city(game: &mut Game, node_id: u32, building_name: Option<String>) {
+    let active_player = game.round.active_player as usize;
+
+    let new_city = City {
+        name: building_name,
+        node_id,
+        player_id: active_player as u32,
+    };
+
+    let nodes = &game.round.board.nodes;
+
+    let err_msg = format!("Could not place a city at node {:?}.", node_id);
+
+    game.round.board.nodes = new_city.place(nodes.clone()).expect(&err_msg);
+}
+
+fn pay_city(game: &mut Game) {
+    let active_player = game.round.active_player as usize;
+
+    let budget = &game.round.board.budgets[active_player];
+
+    // pay for city
+    game.round.board.budgets[active_player] = budget.iter()
+                .zip(game.parameters.building_costs[2].iter())
+                .map(|(b, &c)| b - c)
+                .collect();
+
+    game.round.board.public_budgets[active_player] = game.round.board.public_budgets[active_player].iter()
+                .zip(game.parameters.building_costs[2].iter())
+                .map(|(b, &c)| b.checked_sub(c).unwrap_or(0))
+                .collect();
+}
+
+// one resource per tile adjacent to a setup settlement (as far as the bank can pay)
+fn pay_out_setup(game: &mut Game, node_id: u32) {
+    let active_player = game.round.active_player as usize;
+
+    let mut payouts = vec![vec![0_u32; game.parameters.n_resources as usize]; game.parameters.n_players as usize];
+    for i_tile in &game.parameters.node_tiles_adjacency[node_id as usize] {
+        match game.round.board.tiles[*i_tile].resource {
+            Some(i_resource) if i_resource < game.parameters.n_resources => {
+                payouts[active_player][i_resource as usize] += 1;
+            },
+            _ => (), // desert
+        }
+    }
+
+    let bank_stock = get_bank_stock(&game.round.board.budgets, &game.parameters.resource_supply);
+    let payouts = apply_shortage_rule(&payouts, &bank_stock);
+
+    for (i_resource, n_cards) in payouts[active_player].iter().enumerate() {
+        game.round.board.budgets[active_player][i_resource] += n_cards;
+        game.round.board.public_budgets[active_player][i_resource] += n_cards;
+        game.round.board.total_drawn_resources[active_player][i_resource] += n_cards;
+    }
+}
+
+
+fn apply_discards(game: &mut Game) {
+    for (i_player, discarded, i_log) in game.hidden.pending_discards.clone() {
+        let i_player = i_player as usize;
+
+        if let Some(round) = &mut game.log[i_log].round {
+            round.action = Some(Action::DiscardCards(discarded.clone()));
+        }
+
+        // subtract discarded cards from budget
+        game.round.board.budgets[i_player] = game.round.board.budgets[i_player].iter()
+                                                .zip(discarded.iter())
+                                                .map(|(b, &d)| b - d)
+                                                .collect();
+
+        // discard is public. Comment out to keep it private
+        game.round.board.public_budgets[i_player] = game.round.board.public_budgets[i_player].iter()
+                                                .zip(discarded.iter())
+                                                .map(|(b, &d)| b.checked_sub(d).unwrap_or(0))
+                                                .collect();
+
+        // discarded commodities follow the resources (Cities & Knights)
+        let n_resources = game.parameters.n_resources as usize;
+        for (i_commodity, &d) in discarded.iter().skip(n_resources).enumerate() {
+            game.round.board.commodities[i_player][i_commodity] -= d;
+        }
+    }
+
+    game.hidden.pending_discards = vec![];
+}
+
+
+// move a robber to another tile. The old tile only produces again if no other robber is left on it
+pub fn move_robber(game: &mut Game, i_robber: usize, i_tile: u32) {
+    let i_orig_tile = game.round.board.v_robbers[i_robber];
+    game.round.board.v_robbers[i_robber] = i_tile;
+
+    if !game.round.board.v_robbers.contains(&i_orig_tile) {
+        game.round.board.tiles[i_orig_tile as usize].rng = game.round.board.tile_rng[i_orig_tile as usize];
+    }
+    game.round.board.tiles[i_tile as usize].rng = None;
+}
+
+
+// the active player draws a random resource card from the victim (robber and pirate alike)
+fn steal_resource(game: &mut Game, opt_victim: Option<u32>) {
+    let active_player = game.round.active_player as usize;
+
+    if let Some(i_victim) = opt_victim {
+        // select random resource from victim
+        let total_resources: u32 = game.round.board.budgets[i_victim as usize].iter().sum();
+
+        if total_resources > 0 {
+            let cum_resources: Vec<u32> = game.round.board.budgets[i_victim as usize].iter()
+                                            .scan(0_u32, |acc, &x| {
+                                                *acc += x;
+                                                Some(*acc)
+                                            })
+                                            .collect();
+
+            let mut rng = game.round.board.rng.rng(Stream::Steals);
+            let random_card = rng.gen_range(0..total_resources as i32) as u32;
+
+            // subtract resource from victim's budget
+            let mut resource_type = 0_usize;
+            while cum_resources[resource_type] < random_card {
+                resource_type += 1;
+
+                if cum_resources[resource_type] > random_card {
+                    resource_type -= 1;
+                    break
+                }
+            }
+            
+            if game.round.board.budgets[i_victim as usize][resource_type] != 0 {
+                game.round.board.budgets[i_victim as usize][resource_type] -= 1;
+
+                // add resource to active player's budget
+                game.round.board.budgets[active_player][resource_type] += 1;
+            }
+
+            game.round.outcome = Some(Outcome::RobberOutcome(Some(resource_type as u32) ));
+        } else {
+            game.round.outcome = Some(Outcome::RobberOutcome(None));
+        }
+    }
+}
@@ -0,0 +1,45 @@
+// number of cards of each resource in the bank at the start of a game (24 with the 5-6 player extension)
+pub const RESOURCE_SUPPLY: u32 = 19;
+pub const RESOURCE_SUPPLY_EXTENDED: u32 = 24;
+
+// brick, grain, lumber, ore and wool, as named in the template files
+pub const RESOURCE_NAMES: [&str; 5] = ["B", "G", "L", "O", "W"];
+
+
+// resource cards are never created or destroyed, so the bank holds whatever the players do not
+pub fn get_bank_stock(budgets: &Vec<Vec<u32>>, resource_supply: &Vec<u32>) -> Vec<u32> {
+    resource_supply.iter()
+        .enumerate()
+        .map(|(i_resource, supply)| {
+            let n_held: u32 = budgets.iter().map(|budget| budget[i_resource]).sum();
+            supply.saturating_sub(n_held)
+        })
+        .collect()
+}
+
+
+// shortage rule: if the bank cannot pay every player a resource, nobody receives it.
+// if only one player is owed the resource, that player receives whatever is left
+pub fn apply_shortage_rule(payouts: &Vec<Vec<u32>>, bank_stock: &Vec<u32>) -> Vec<Vec<u32>> {
+    let mut payouts = payouts.clone();
+
+    for (i_resource, stock) in bank_stock.iter().enumerate() {
+        let n_owed: u32 = payouts.iter().map(|payout| payout[i_resource]).sum();
+
+        if n_owed <= *stock {
+            continue
+        }
+
+        let n_recipients = payouts.iter().filter(|payout| payout[i_resource] > 0).count();
+
+        for payout in payouts.iter_mut() {
+            if n_recipients == 1 {
+                payout[i_resource] = payout[i_resource].min(*stock);
+            } else {
+                payout[i_resource] = 0;
+            }
+        }
+    }
+
+    payouts
+}
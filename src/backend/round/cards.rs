@@ -1,3 +1,4 @@
+use rand::{rngs::StdRng, seq::SliceRandom};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Deserialize, PartialEq, Serialize)]
@@ -7,4 +8,38 @@ pub enum CardType {
     RoadsCard(u32, u32, u32, u32),
     PlentyCard(u32, u32),
     MonopolyCard(u32),
-}
\ No newline at end of file
+}
+
+// index of every card type in the deck and hands. Templates name the card types (dev_cards.csv)
+pub const VP_CARD: usize = 0;
+pub const KNIGHT_CARD: usize = 1;
+pub const ROADS_CARD: usize = 2;
+pub const PLENTY_CARD: usize = 3;
+pub const MONOPOLY_CARD: usize = 4;
+
+pub const DEV_CARD_NAMES: [&str; 5] = ["VP", "Knight", "Roads", "Plenty", "Monopoly"];
+
+
+impl CardType {
+    pub fn get_id(&self) -> usize {
+        match self {
+            CardType::VPCard => VP_CARD,
+            CardType::KnightCard(_, _, _) => KNIGHT_CARD,
+            CardType::RoadsCard(_, _, _, _) => ROADS_CARD,
+            CardType::PlentyCard(_, _) => PLENTY_CARD,
+            CardType::MonopolyCard(_) => MONOPOLY_CARD,
+        }
+    }
+}
+
+// one card (its type index) per undrawn card, shuffled. Cards are drawn from the end of the deck
+pub fn get_shuffled_dev_deck(undrawn_dev_cards: &Vec<u32>, mut rng: StdRng) -> Vec<u32> {
+    let mut deck: Vec<u32> = undrawn_dev_cards.iter()
+                                .enumerate()
+                                .flat_map(|(card_type, &n_cards)| vec![card_type as u32; n_cards as usize])
+                                .collect();
+
+    deck.shuffle(&mut rng);
+
+    deck
+}
@@ -0,0 +1,559 @@
+use rand::{rngs::StdRng, seq::SliceRandom};
+use serde::{Deserialize, Serialize};
+
+use crate::backend::setup::{game::Game, knight::Knight, node_status::NodeStatus, rng_service::Stream, ruleset::Ruleset};
+
+use super::{action::{place_road, Action}, bank::get_bank_stock, legal::{get_free_road_pairs, is_pirated}, phase::Phase};
+
+// paper (science), cloth (trade) and coin (politics). Commodities, improvement tracks, metropolises and progress decks share this order
+pub const N_COMMODITIES: usize = 3;
+
+// progress cards in play (a subset of the expansion's cards). Their index is their type in the hands
+pub const ROAD_BUILDING: usize = 0;
+pub const IRRIGATION: usize = 1;
+pub const MINING: usize = 2;
+pub const PRINTER: usize = 3;
+pub const RESOURCE_MONOPOLY: usize = 4;
+pub const TRADE_MONOPOLY: usize = 5;
+pub const WARLORD: usize = 6;
+pub const CONSTITUTION: usize = 7;
+pub const N_PROGRESS_CARD_TYPES: usize = 8;
+
+pub const PROGRESS_CARD_NAMES: [&str; N_PROGRESS_CARD_TYPES] = ["Road Building", "Irrigation", "Mining", "Printer", "Resource Monopoly", "Trade Monopoly", "Warlord", "Constitution"];
+
+// cards of every type in the science, trade and politics decks. Printer and Constitution are victory points
+pub const PROGRESS_DECKS: [[u32; N_PROGRESS_CARD_TYPES]; N_COMMODITIES] = [
+    [2, 2, 2, 1, 0, 0, 0, 0],
+    [0, 0, 0, 0, 4, 2, 0, 0],
+    [0, 0, 0, 0, 0, 0, 2, 1],
+];
+
+// the barbarian ship attacks once it has sailed this far
+pub const BARBARIAN_DISTANCE: u32 = 7;
+
+pub const METROPOLIS_LEVEL: u32 = 4;
+pub const MAX_IMPROVEMENT_LEVEL: u32 = 5;
+pub const MAX_KNIGHT_LEVEL: u32 = 3;
+// a player may own this many knights of every level
+pub const MAX_KNIGHTS_PER_LEVEL: usize = 2;
+pub const MAX_PROGRESS_CARDS: u32 = 4;
+
+// mighty knights need this level of politics
+pub const MIGHTY_KNIGHT_POLITICS: u32 = 3;
+
+// the trading house (this level of trade) trades commodities 2:1
+pub const TRADING_HOUSE_LEVEL: u32 = 3;
+
+// irrigation and mining pay this many grain or ore per field or mountain next to the player's buildings
+pub const HARVEST_PER_TILE: u32 = 2;
+// resource monopoly takes up to this many cards from every other player
+pub const MAX_RESOURCE_MONOPOLY: u32 = 2;
+
+// knights and promotions cost wool and ore, activation costs grain (resources B, G, L, O, W)
+pub const KNIGHT_COSTS: [u32; 5] = [0, 0, 0, 1, 1];
+pub const ACTIVATION_COSTS: [u32; 5] = [0, 1, 0, 0, 0];
+
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Serialize)]
+pub enum ProgressCard {
+    RoadBuilding(u32, u32, u32, u32),
+    Irrigation,
+    Mining,
+    ResourceMonopoly(u32),
+    TradeMonopoly(u32),
+    Warlord,
+}
+
+impl ProgressCard {
+    pub fn get_id(&self) -> usize {
+        match self {
+            ProgressCard::RoadBuilding(_, _, _, _) => ROAD_BUILDING,
+            ProgressCard::Irrigation => IRRIGATION,
+            ProgressCard::Mining => MINING,
+            ProgressCard::ResourceMonopoly(_) => RESOURCE_MONOPOLY,
+            ProgressCard::TradeMonopoly(_) => TRADE_MONOPOLY,
+            ProgressCard::Warlord => WARLORD,
+        }
+    }
+}
+
+// one card (its type index) per card of the track, shuffled. Cards are drawn from the end of the deck
+pub fn get_shuffled_progress_decks(mut rng: StdRng) -> Vec<Vec<u32>> {
+    PROGRESS_DECKS.iter()
+        .map(|n_cards| {
+            let mut deck: Vec<u32> = n_cards.iter()
+                                        .enumerate()
+                                        .flat_map(|(card_type, &n)| vec![card_type as u32; n as usize])
+                                        .collect();
+
+            deck.shuffle(&mut rng);
+
+            deck
+        })
+        .collect()
+}
+
+pub fn get_progress_track(card_type: usize) -> usize {
+    PROGRESS_DECKS.iter().position(|n_cards| n_cards[card_type] > 0).expect("Unknown progress card.")
+}
+
+// cities on forests, pastures and mountains produce paper, cloth and coin
+pub fn get_commodity(i_resource: u32) -> Option<usize> {
+    match i_resource {
+        2 => Some(0),
+        4 => Some(1),
+        3 => Some(2),
+        _ => None,
+    }
+}
+
+// two victory points per metropolis, one per defender of Catan and one per victory point progress card
+pub fn get_cities_knights_score(i_player: u32, metropolises: &Vec<Option<u32>>, defender_points: &Vec<u32>, progress_points: &Vec<u32>) -> u32 {
+    let n_metropolises = metropolises.iter().filter(|&&holder| holder == Some(i_player)).count() as u32;
+
+    2 * n_metropolises + defender_points[i_player as usize] + progress_points[i_player as usize]
+}
+
+// resource cards and commodities count towards the hand limit and are discarded alike (commodities after the resources)
+pub fn get_hand(game: &Game, i_player: u32) -> Vec<u32> {
+    let mut hand = game.round.board.budgets[i_player as usize].clone();
+
+    if game.parameters.ruleset == Ruleset::CitiesAndKnights {
+        hand.extend(&game.round.board.commodities[i_player as usize]);
+    }
+
+    hand
+}
+
+// the first player to reach METROPOLIS_LEVEL gets the metropolis. It only changes hands if another player's level strictly exceeds the holder's
+pub fn update_metropolises(improvements: &Vec<Vec<u32>>, metropolises: &Vec<Option<u32>>) -> Vec<Option<u32>> {
+    (0..N_COMMODITIES).map(|i_track| {
+        let top_level = improvements.iter().map(|levels| levels[i_track]).max().unwrap_or(0);
+
+        let top_players: Vec<u32> = (0..improvements.len() as u32)
+                                        .filter(|&i_player| improvements[i_player as usize][i_track] == top_level)
+                                        .collect();
+
+        match metropolises[i_track] {
+            Some(i_holder) => {
+                if top_level > improvements[i_holder as usize][i_track] && top_players.len() == 1 {
+                    Some(top_players[0])
+                } else {
+                    Some(i_holder)
+                }
+            },
+            None => {
+                if top_level >= METROPOLIS_LEVEL && top_players.len() == 1 {
+                    Some(top_players[0])
+                } else {
+                    None
+                }
+            },
+        }
+    })
+    .collect()
+}
+
+
+impl Game {
+    // the event die is thrown together with the production dice. Ship faces (1 to 3) move the barbarians,
+    // gate faces (science, trade, politics) hand out progress cards depending on the red die
+    pub fn resolve_event_die(&mut self) {
+        let rng = self.round.board.rng.rng(Stream::Events);
+        self.round.board.event_dice = self.round.board.event_dice.clone().throw(rng);
+
+        let event_face = match self.round.board.event_dice.draw {
+            Some(face) => face,
+            None => return,
+        };
+
+        if event_face <= 3 {
+            self.round.board.barbarian_position += 1;
+
+            if self.round.board.barbarian_position >= BARBARIAN_DISTANCE {
+                self.barbarian_attack();
+            }
+        } else {
+            let i_track = (event_face - 4) as usize;
+            let red_die = self.round.board.dice.faces().first().copied().unwrap_or(0);
+
+            for i_seat in 0..self.parameters.n_players {
+                let i_player = (self.round.throwing_player + i_seat) % self.parameters.n_players;
+                let level = self.round.board.improvements[i_player as usize][i_track];
+
+                if level > 0 && red_die <= level + 1 {
+                    draw_progress_card(self, i_player, i_track);
+                }
+            }
+        }
+    }
+
+    // the barbarians are as strong as the number of cities. Active knights defend Catan
+    fn barbarian_attack(&mut self) {
+        let n_players = self.parameters.n_players as usize;
+
+        let cities: Vec<u32> = (0..n_players as u32)
+            .map(|i_player| self.round.board.nodes.iter().filter(|node| node.node_status == NodeStatus::Citied(i_player)).count() as u32)
+            .collect();
+
+        let mut contributions = vec![0_u32; n_players];
+        for knight in &self.round.board.knights {
+            if knight.active {
+                contributions[knight.player as usize] += knight.level;
+            }
+        }
+
+        let barbarian_strength: u32 = cities.iter().sum();
+        let defense: u32 = contributions.iter().sum();
+
+        if defense >= barbarian_strength {
+            // the unique strongest defender becomes a defender of Catan. Tied defenders draw a progress card instead
+            let top_contribution = contributions.iter().copied().max().unwrap_or(0);
+
+            if top_contribution > 0 {
+                let defenders: Vec<u32> = (0..n_players as u32)
+                                            .filter(|&i_player| contributions[i_player as usize] == top_contribution)
+                                            .collect();
+
+                if defenders.len() == 1 {
+                    self.round.board.defender_points[defenders[0] as usize] += 1;
+                } else {
+                    for i_player in defenders {
+                        let i_track = self.round.board.progress_deck.iter()
+                                        .enumerate()
+                                        .max_by_key(|&(_, n_cards)| n_cards)
+                                        .map(|(i_track, _)| i_track)
+                                        .unwrap_or(0);
+
+                        draw_progress_card(self, i_player, i_track);
+                    }
+                }
+            }
+        } else {
+            // the weakest players with a city that is not a metropolis lose one city
+            let exposed: Vec<u32> = (0..n_players as u32)
+                                        .filter(|&i_player| {
+                                            let n_metropolises = self.round.board.metropolises.iter().filter(|&&holder| holder == Some(i_player)).count() as u32;
+                                            cities[i_player as usize] > n_metropolises
+                                        })
+                                        .collect();
+
+            let min_contribution = exposed.iter().map(|&i_player| contributions[i_player as usize]).min();
+
+            if let Some(min_contribution) = min_contribution {
+                for &i_player in exposed.iter().filter(|&&i_player| contributions[i_player as usize] == min_contribution) {
+                    pillage_city(self, i_player);
+                }
+            }
+        }
+
+        // after every attack, the knights are deactivated and the barbarians sail again
+        for knight in self.round.board.knights.iter_mut() {
+            knight.active = false;
+        }
+
+        self.round.board.barbarian_position = 0;
+    }
+}
+
+
+// knights, activations, promotions and city improvements the active player can afford
+pub fn get_legal_cities_knights(game: &Game, mut legal_actions: Vec<Action>) -> Vec<Action> {
+    if game.parameters.ruleset != Ruleset::CitiesAndKnights {
+        return legal_actions
+    }
+
+    let board = &game.round.board;
+    let active_player = game.round.active_player;
+    let budget = &board.budgets[active_player as usize];
+
+    let knight_affordable = budget.iter().zip(KNIGHT_COSTS.iter()).all(|(b, c)| b >= c);
+    let activation_affordable = budget.iter().zip(ACTIVATION_COSTS.iter()).all(|(b, c)| b >= c);
+
+    let n_knights = |level: u32| board.knights.iter().filter(|knight| knight.player == active_player && knight.level == level).count();
+
+    // build a basic knight on an empty node along an own road
+    if knight_affordable && n_knights(1) < MAX_KNIGHTS_PER_LEVEL {
+        for node in &board.nodes {
+            let node_empty = matches!(node.node_status, NodeStatus::Free | NodeStatus::Adjacent);
+
+            let player_road = match &node.roads {
+                Some(v_roads) => v_roads.iter().any(|(player, _)| *player == active_player),
+                None => false,
+            };
+
+            let knight_present = board.knights.iter().any(|knight| knight.node == node.id);
+
+            if node_empty && player_road && !knight_present {
+                legal_actions.push(Action::BuildKnight(node.id));
+            }
+        }
+    }
+
+    for knight in board.knights.iter().filter(|knight| knight.player == active_player) {
+        // activate an idle knight
+        if !knight.active && activation_affordable {
+            legal_actions.push(Action::ActivateKnight(knight.node));
+        }
+
+        // promote a knight (mighty knights need politics)
+        let promotion_possible = knight.level < MAX_KNIGHT_LEVEL
+                                    && n_knights(knight.level + 1) < MAX_KNIGHTS_PER_LEVEL
+                                    && (knight.level + 1 < MAX_KNIGHT_LEVEL || board.improvements[active_player as usize][2] >= MIGHTY_KNIGHT_POLITICS);
+
+        if knight_affordable && promotion_possible {
+            legal_actions.push(Action::PromoteKnight(knight.node));
+        }
+    }
+
+    // city improvements need a city and cost one more commodity per level
+    let has_city = board.nodes.iter().any(|node| node.node_status == NodeStatus::Citied(active_player));
+
+    if has_city {
+        for i_track in 0..N_COMMODITIES {
+            let level = board.improvements[active_player as usize][i_track];
+
+            if level < MAX_IMPROVEMENT_LEVEL && board.commodities[active_player as usize][i_track] >= level + 1 {
+                legal_actions.push(Action::BuildImprovement(i_track as u32));
+            }
+        }
+    }
+
+    // progress cards are played during the own building phase
+    if game.round.phase == Phase::Building {
+        legal_actions = get_legal_progress_cards(game, legal_actions);
+    }
+
+    legal_actions
+}
+
+fn get_legal_progress_cards(game: &Game, mut legal_actions: Vec<Action>) -> Vec<Action> {
+    let active_player = game.round.active_player as usize;
+
+    for (card_type, &n_held) in game.round.board.progress_cards[active_player].iter().enumerate() {
+        if n_held == 0 {
+            continue
+        }
+
+        match card_type {
+            ROAD_BUILDING => {
+                for (s1, e1, s2, e2) in get_free_road_pairs(game) {
+                    legal_actions.push(Action::PlayProgressCard(ProgressCard::RoadBuilding(s1, e1, s2, e2)));
+                }
+            },
+            IRRIGATION => legal_actions.push(Action::PlayProgressCard(ProgressCard::Irrigation)),
+            MINING => legal_actions.push(Action::PlayProgressCard(ProgressCard::Mining)),
+            RESOURCE_MONOPOLY => {
+                for i_resource in 0..game.parameters.n_resources {
+                    legal_actions.push(Action::PlayProgressCard(ProgressCard::ResourceMonopoly(i_resource)));
+                }
+            },
+            TRADE_MONOPOLY => {
+                for i_commodity in 0..N_COMMODITIES as u32 {
+                    legal_actions.push(Action::PlayProgressCard(ProgressCard::TradeMonopoly(i_commodity)));
+                }
+            },
+            WARLORD => legal_actions.push(Action::PlayProgressCard(ProgressCard::Warlord)),
+            _ => (),
+        }
+    }
+
+    legal_actions
+}
+
+// commodities trade 4:1 with the bank, 3:1 at an open generic harbor and 2:1 with the trading house
+pub fn get_commodity_trade_rate(game: &Game, i_player: u32) -> u32 {
+    if game.round.board.improvements[i_player as usize][1] >= TRADING_HOUSE_LEVEL {
+        return 2
+    }
+
+    let generic_harbor = game.round.board.nodes.iter()
+                            .filter_map(|node| node.harbor.as_ref())
+                            .any(|harbor| harbor.player == Some(i_player) && harbor.harbor_type >= game.parameters.n_resources && !is_pirated(game, harbor.nodes));
+
+    if generic_harbor {
+        3
+    } else {
+        4
+    }
+}
+
+pub fn get_legal_commodity_trades(game: &Game, mut legal_actions: Vec<Action>) -> Vec<Action> {
+    if game.parameters.ruleset != Ruleset::CitiesAndKnights {
+        return legal_actions
+    }
+
+    let board = &game.round.board;
+    let active_player = game.round.active_player;
+    let rate = get_commodity_trade_rate(game, active_player);
+
+    for i_commodity in 0..N_COMMODITIES {
+        if board.commodities[active_player as usize][i_commodity] >= rate {
+            for r_demanded in (0..game.parameters.n_resources).filter(|&r| board.bank_stock[r as usize] > 0) {
+                legal_actions.push(Action::CommodityTrade(i_commodity as u32, r_demanded));
+            }
+        }
+    }
+
+    legal_actions
+}
+
+pub fn trade_commodity(game: &mut Game, i_commodity: u32, r_demanded: u32) {
+    let active_player = game.round.active_player as usize;
+    let rate = get_commodity_trade_rate(game, active_player as u32);
+
+    game.round.board.commodities[active_player][i_commodity as usize] -= rate;
+    game.round.board.budgets[active_player][r_demanded as usize] += 1;
+    game.round.board.public_budgets[active_player][r_demanded as usize] += 1;
+}
+
+
+pub fn place_and_pay_knight(game: &mut Game, node_id: u32) {
+    let active_player = game.round.active_player;
+
+    game.round.board.knights.push(Knight {
+        player: active_player,
+        node: node_id,
+        level: 1,
+        active: false,
+    });
+
+    pay(game, &KNIGHT_COSTS);
+}
+
+pub fn activate_knight(game: &mut Game, node_id: u32) {
+    for knight in game.round.board.knights.iter_mut() {
+        if knight.node == node_id {
+            knight.active = true;
+        }
+    }
+
+    pay(game, &ACTIVATION_COSTS);
+}
+
+pub fn promote_knight(game: &mut Game, node_id: u32) {
+    for knight in game.round.board.knights.iter_mut() {
+        if knight.node == node_id {
+            knight.level += 1;
+        }
+    }
+
+    pay(game, &KNIGHT_COSTS);
+}
+
+pub fn play_progress_card(game: &mut Game, card: ProgressCard) {
+    let active_player = game.round.active_player as usize;
+
+    match card {
+        ProgressCard::RoadBuilding(s1, e1, s2, e2) => {
+            place_road(game, s1, e1);
+
+            // the same road twice: the player had only one road left
+            if (s2, e2) != (s1, e1) {
+                place_road(game, s2, e2);
+            }
+        },
+        ProgressCard::Irrigation => harvest(game, 1),
+        ProgressCard::Mining => harvest(game, 3),
+        ProgressCard::ResourceMonopoly(i_resource) => {
+            for i_player in (0..game.parameters.n_players as usize).filter(|&i_player| i_player != active_player) {
+                let n_taken = game.round.board.budgets[i_player][i_resource as usize].min(MAX_RESOURCE_MONOPOLY);
+
+                game.round.board.budgets[i_player][i_resource as usize] -= n_taken;
+                game.round.board.public_budgets[i_player][i_resource as usize] = game.round.board.public_budgets[i_player][i_resource as usize].saturating_sub(n_taken);
+
+                game.round.board.budgets[active_player][i_resource as usize] += n_taken;
+                game.round.board.public_budgets[active_player][i_resource as usize] += n_taken;
+            }
+        },
+        ProgressCard::TradeMonopoly(i_commodity) => {
+            for i_player in (0..game.parameters.n_players as usize).filter(|&i_player| i_player != active_player) {
+                if game.round.board.commodities[i_player][i_commodity as usize] > 0 {
+                    game.round.board.commodities[i_player][i_commodity as usize] -= 1;
+                    game.round.board.commodities[active_player][i_commodity as usize] += 1;
+                }
+            }
+        },
+        ProgressCard::Warlord => {
+            for knight in game.round.board.knights.iter_mut().filter(|knight| knight.player == active_player as u32) {
+                knight.active = true;
+            }
+        },
+    }
+
+    // played cards go back under the deck of their track
+    let card_type = card.get_id();
+    let i_track = get_progress_track(card_type);
+
+    game.round.board.progress_cards[active_player][card_type] -= 1;
+    game.hidden.progress_decks[i_track].insert(0, card_type as u32);
+    game.round.board.progress_deck[i_track] += 1;
+}
+
+// irrigation (grain) and mining (ore): HARVEST_PER_TILE cards for every tile of the resource next to the player's buildings, as far as the bank can pay
+fn harvest(game: &mut Game, i_resource: u32) {
+    let active_player = game.round.active_player;
+
+    let n_tiles = game.round.board.tiles.iter()
+                    .filter(|tile| tile.resource == Some(i_resource))
+                    .filter(|tile| tile.nodes.iter().any(|&i_node| matches!(game.round.board.nodes[i_node as usize].node_status, NodeStatus::Settled(owner) | NodeStatus::Citied(owner) if owner == active_player)))
+                    .count() as u32;
+
+    let bank_stock = get_bank_stock(&game.round.board.budgets, &game.parameters.resource_supply);
+    let n_cards = (HARVEST_PER_TILE * n_tiles).min(bank_stock[i_resource as usize]);
+
+    game.round.board.budgets[active_player as usize][i_resource as usize] += n_cards;
+    game.round.board.public_budgets[active_player as usize][i_resource as usize] += n_cards;
+    game.round.board.total_drawn_resources[active_player as usize][i_resource as usize] += n_cards;
+}
+
+// improvements are paid in commodities of their own track
+pub fn build_improvement(game: &mut Game, i_track: u32) {
+    let active_player = game.round.active_player as usize;
+    let level = game.round.board.improvements[active_player][i_track as usize];
+
+    game.round.board.commodities[active_player][i_track as usize] -= level + 1;
+    game.round.board.improvements[active_player][i_track as usize] += 1;
+}
+
+fn pay(game: &mut Game, costs: &[u32; 5]) {
+    let active_player = game.round.active_player as usize;
+
+    game.round.board.budgets[active_player] = game.round.board.budgets[active_player].iter()
+                        .zip(costs.iter())
+                        .map(|(b, &c)| b - c)
+                        .collect();
+
+    game.round.board.public_budgets[active_player] = game.round.board.public_budgets[active_player].iter()
+                        .zip(costs.iter())
+                        .map(|(b, &c)| b.checked_sub(c).unwrap_or(0))
+                        .collect();
+}
+
+// the top card of the track's hidden deck. Victory point cards are revealed at once, the others are held (up to MAX_PROGRESS_CARDS)
+fn draw_progress_card(game: &mut Game, i_player: u32, i_track: usize) {
+    let n_held: u32 = game.round.board.progress_cards[i_player as usize].iter().sum();
+
+    if n_held >= MAX_PROGRESS_CARDS {
+        return
+    }
+
+    if let Some(card_type) = game.hidden.progress_decks[i_track].pop() {
+        game.round.board.progress_deck[i_track] -= 1;
+
+        match card_type as usize {
+            PRINTER | CONSTITUTION => game.round.board.progress_points[i_player as usize] += 1,
+            card_type => game.round.board.progress_cards[i_player as usize][card_type] += 1,
+        }
+    }
+}
+
+// the pillaged city (the one on the lowest node) is reduced to a settlement
+fn pillage_city(game: &mut Game, i_player: u32) {
+    let i_node = game.round.board.nodes.iter().position(|node| node.node_status == NodeStatus::Citied(i_player));
+
+    if let Some(i_node) = i_node {
+        game.round.board.nodes[i_node].city = None;
+        game.round.board.nodes[i_node].node_status = NodeStatus::Settled(i_player);
+    }
+}
@@ -1 +1,38 @@
+// an army needs at least this many knights to earn the largest army
+pub const MIN_LARGEST_ARMY: u32 = 3;
 
+
+// the holder keeps the largest army until another player's army strictly exceeds theirs.
+// without a holder, the title goes to the unique largest army of at least MIN_LARGEST_ARMY knights (ties award nobody)
+pub fn update_largest_army(armies: &Vec<u32>, prev_largest_army: &Option<(u32, u32)>) -> Option<(u32, u32)> {
+
+    let size_largest_army = armies.iter().fold(0, |acc, &x| acc.max(x));
+
+    let largest_armies: Vec<usize> = armies.iter()
+                                        .enumerate()
+                                        .filter(|&(_, &army)| army == size_largest_army)
+                                        .map(|(i_player, _)| i_player)
+                                        .collect();
+
+    match prev_largest_army {
+        Some((i_holder, _)) => {
+            let holder_army = armies[*i_holder as usize];
+
+            if size_largest_army > holder_army && largest_armies.len() == 1 {
+                Some((largest_armies[0] as u32, size_largest_army))
+            } else if size_largest_army > holder_army {
+                // several challengers overtook the holder at once: nobody has the strictly largest army
+                None
+            } else {
+                Some((*i_holder, holder_army))
+            }
+        },
+        None => {
+            if size_largest_army >= MIN_LARGEST_ARMY && largest_armies.len() == 1 {
+                Some((largest_armies[0] as u32, size_largest_army))
+            } else {
+                None
+            }
+        },
+    }
+}
@@ -1,6 +1,6 @@
-use crate::backend::{round::phase::Phase::{Building, FirstCardPhase, RobberDiscard, RobberMove, SetUp, TradingQuote, TradingResponse, SecondCardPhase, Terminal}, setup::{game::Game, node_status::NodeStatus, player::PlayerType}};
+use crate::backend::{round::phase::Phase::{Building, FirstCardPhase, GoldChoice, RobberDiscard, RobberMove, SetUp, TradingQuote, TradingResponse, SecondCardPhase, SpecialBuilding, Terminal}, setup::{edge::make_edge, game::Game, node_status::NodeStatus, player::PlayerType, terrain::Terrain}};
 
-use super::{action::{Action, Quote}, cards::CardType};
+use super::{action::{Action, Quote}, cards::{CardType, KNIGHT_CARD, MONOPOLY_CARD, PLENTY_CARD, ROADS_CARD, VP_CARD}, cities_knights::{get_hand, get_legal_cities_knights, get_legal_commodity_trades}, pieces::{get_remaining_pieces, get_remaining_ships}};
 
 impl Game {
     pub fn get_legal_actions(&self) ->Vec<Action> {
@@ -17,14 +17,16 @@ impl Game {
                 if self.round.phase_count / self.parameters.n_players < self.parameters.n_setup_rounds {
                     
                     for node in &board.nodes {
-                        if node.node_status == NodeStatus::Free {
+                        // settlements and roads need land
+                        if node.node_status == NodeStatus::Free && self.parameters.is_land_node(node.id) {
                             let node_id = node.id;
 
                             for neighbor in &node.neighbors {
                                 match &neighbor {
-                                    Some(n) => {
+                                    Some(n) if self.parameters.is_land_edge(node_id, *n) => {
                                         legal_actions.push(Action::SetUpMove(node_id, *n))
                                     },
+                                    Some(_) => (),
                                     None => (),
                                 }
                             }
@@ -37,10 +39,12 @@ impl Game {
             },
             
             RobberDiscard => {
-                let sum_budget = &budget.iter().sum::<u32>();
-                if sum_budget > &7 {
+                // commodities count towards the hand limit (Cities & Knights)
+                let hand = get_hand(self, active_player as u32);
+                let sum_budget = &hand.iter().sum::<u32>();
+                if sum_budget > &self.parameters.hand_limit {
                     // find all combinations of resources which are within budget and add up to half of all the player's resourcess
-                    let possible_discards = enumerate_discards(budget);
+                    let possible_discards = enumerate_discards(&hand);
 
                     // add each combination as a legal action
                     for v_discard in possible_discards {
@@ -55,6 +59,15 @@ impl Game {
                 legal_actions = get_legal_robber_moves(self, legal_actions)
             },
 
+            GoldChoice => {
+                // resources of choice, as far as the bank can pay
+                let n_owed = self.round.gold_owed[active_player].min(board.bank_stock.iter().sum());
+
+                for v_chosen in enumerate_hands(&board.bank_stock, n_owed) {
+                    legal_actions.push(Action::ChooseGold(v_chosen));
+                }
+            },
+
             FirstCardPhase => {
                 legal_actions = get_legal_cards(self, legal_actions)      
             },
@@ -68,6 +81,10 @@ impl Game {
                     // check whether resource can be traded with the bank
                     if b >= &4 {
                         for resource_demanded in 0..*n_resources {
+                            // the bank must hold the demanded resource
+                            if board.bank_stock[resource_demanded as usize] == 0 {
+                                continue
+                            }
                             legal_actions.push(Action::BankTrade(i_resource as u32, resource_demanded));
                         }
                     }
@@ -114,10 +131,11 @@ impl Game {
                         Some(harbor) => {
                             match &harbor.player {
                                 Some(i_player) => {
-                                    if i_player == &(active_player as u32) {
+                                    // a pirate on the harbor's edge closes the harbor
+                                    if i_player == &(active_player as u32) && !is_pirated(self, harbor.nodes) {
                                         if harbor.harbor_type < *n_resources {
                                             if self.round.board.budgets[active_player][harbor.harbor_type as usize] >= 2 { // check whether the player has enough resources
-                                                for r_demanded in 0..*n_resources {
+                                                for r_demanded in (0..*n_resources).filter(|&r| board.bank_stock[r as usize] > 0) {
                                                     legal_actions.push(Action::HarborTrade(harbor.harbor_type, harbor.harbor_type, r_demanded));
                                                 }
                                             }
@@ -125,7 +143,7 @@ impl Game {
                                         } else {
                                             for r_supplied in 0..*n_resources {
                                                 if self.round.board.budgets[active_player][r_supplied as usize] >= 3 {
-                                                    for r_demanded in 0..*n_resources {
+                                                    for r_demanded in (0..*n_resources).filter(|&r| board.bank_stock[r as usize] > 0) {
                                                         legal_actions.push(Action::HarborTrade(harbor.harbor_type, r_supplied, r_demanded))
                                                     }
                                                 }
@@ -140,6 +158,8 @@ impl Game {
                     }
                 }
 
+                // commodities (Cities & Knights)
+                legal_actions = get_legal_commodity_trades(self, legal_actions);
 
             },
 
@@ -164,68 +184,11 @@ impl Game {
             },
 
             Building => {
-                legal_actions.push(Action::NoBuying); // do not build
-
-                let building_costs = &self.parameters.building_costs;
-
-                // build road
-                let road_affordable = budget.iter()
-                                            .zip(&building_costs[0])
-                                            .all(|(b, c)| b >= c);
-
-                if road_affordable { // check affordability
-                    legal_actions = get_legal_roads(self, legal_actions);
-                }
-
-                // build settlement
-                let settlement_affordable = budget.iter()
-                                                        .zip(&building_costs[1])
-                                                        .all(|(b,c)| b >= c);
-
-                if settlement_affordable { // check affordability
-                    for node in &board.nodes {
-                        if node.node_status == NodeStatus::Free { // a new settlement only legal if node is free (not settled or adjacent to settled)
-                            let mut player_road = false;    // a new settlement needs to be attached to an existing road
-                            match &node.roads {         
-                                Some(v_roads) => {
-                                    for (player, _) in v_roads {
-                                        if active_player == *player as usize {
-                                            player_road = true;
-                                            break
-                                        }
-                                    }
-                                },
-                                None => continue,
-                            }
-                            
-                            if player_road {
-                                legal_actions.push(Action::BuildSettlement(node.id));
-                            }
-                        }
-                    }
-                }
-                    
-                // build city
-                let city_affordable = budget.iter()
-                                                .zip(&building_costs[2])
-                                                .all(|(b,c)| b >= c);
-                if city_affordable { // check affordability
-                    for node in &board.nodes {
-                        if node.node_status == NodeStatus::Settled(active_player as u32) { // new city only legal if the node has a settlement by the same player
-                            legal_actions.push(Action::BuildCity(node.id));
-                        }
-                    }
-                }
-
-                // buy development card
-                let dev_card_affordable = budget.iter()
-                                                .zip(&building_costs[3])
-                                                .all(|(b,c)| b >= c);
-                
-                if dev_card_affordable { // check affordability
-                    legal_actions.push(Action::BuyDevCard);
-                }
+                legal_actions = get_legal_builds(self, legal_actions);
+            },
 
+            SpecialBuilding => {
+                legal_actions = get_legal_builds(self, legal_actions);
             },
             
             SecondCardPhase => {
@@ -253,10 +216,17 @@ impl Game {
 
 
 // enumerate all ``hands'' which add up to half of all resources and ``fit'' into the current budget
-// algorithm: recursive backtrack
 fn enumerate_discards(budget: &Vec<u32>) -> Vec<Vec<u32>> {
 
     let target_sum = budget.iter().sum::<u32>() / 2;
+
+    enumerate_hands(budget, target_sum)
+}
+
+// enumerate all ``hands'' which add up to the target and ``fit'' into the budget
+// algorithm: recursive backtrack
+fn enumerate_hands(budget: &Vec<u32>, target_sum: u32) -> Vec<Vec<u32>> {
+
     let mut results = Vec::new();
     let mut current = vec![0; budget.len()];
 
@@ -294,15 +264,15 @@ fn get_legal_robber_moves(game: &Game, mut legal_actions: Vec<Action>) -> Vec<Ac
     let tiles = &board.tiles;
 
     for (i_tile, tile) in tiles.iter().enumerate() {
-        let mut is_robber_tile = false;
-        for robber_tile in &board.v_robbers {
-            if (i_tile as u32 == *robber_tile) || (tile.rng == None) {
-                is_robber_tile = true;
-                break;
-            }
-        }
+        let is_robber_tile = board.v_robbers.contains(&(i_tile as u32));
+
+        // tiles without a number (the desert) only if the variant allows it
+        let is_desert = board.tile_rng[i_tile] == None;
 
-        if is_robber_tile {
+        // the robber stays on land
+        let is_sea = game.parameters.get_terrain(i_tile) == Terrain::Sea;
+
+        if is_robber_tile || is_sea || (is_desert & !game.parameters.robber_on_desert) {
             continue
         }
 
@@ -315,12 +285,12 @@ fn get_legal_robber_moves(game: &Game, mut legal_actions: Vec<Action>) -> Vec<Ac
                 NodeStatus::Free => continue,
                 NodeStatus::Adjacent => continue,
                 NodeStatus::Settled(i_player) => {
-                    if *i_player != active_player as u32 {
+                    if (*i_player != active_player as u32) & !is_protected(game, *i_player) {
                         adjacent_players.push(*i_player)
                     }
                 },
                 NodeStatus::Citied(i_player) => {
-                    if *i_player != active_player as u32 {
+                    if (*i_player != active_player as u32) & !is_protected(game, *i_player) {
                         adjacent_players.push(*i_player)
                     }
                 },
@@ -340,10 +310,70 @@ fn get_legal_robber_moves(game: &Game, mut legal_actions: Vec<Action>) -> Vec<Ac
 
     }
 
+    legal_actions = get_legal_pirate_moves(game, legal_actions);
+
+    legal_actions
+}
+
+// pirates move along the coast instead of onto tiles and rob the players settled at either end of their edge
+fn get_legal_pirate_moves(game: &Game, mut legal_actions: Vec<Action>) -> Vec<Action> {
+
+    let board = &game.round.board;
+    let active_player = game.round.active_player;
+
+    if board.v_pirates.is_empty() {
+        return legal_actions
+    }
+
+    for (start_node, end_node) in game.parameters.get_coast_edges() {
+        let i_edge = game.parameters.edge_map[&make_edge(start_node, end_node)] as u32;
+
+        if board.v_pirates.contains(&i_edge) {
+            continue
+        }
+
+        let mut adjacent_players: Vec<u32> = vec![];
+        for i_node in [start_node, end_node] {
+            match board.nodes[i_node as usize].node_status {
+                NodeStatus::Settled(i_player) | NodeStatus::Citied(i_player) => {
+                    if (i_player != active_player) & !is_protected(game, i_player) & !adjacent_players.contains(&i_player) {
+                        adjacent_players.push(i_player)
+                    }
+                },
+                _ => continue,
+            }
+        }
+
+        for pirate_id in 0..board.v_pirates.len() {
+            if adjacent_players.is_empty() {
+                legal_actions.push(Action::Pirate(pirate_id as u32, i_edge, None))
+            } else {
+                for player in &adjacent_players {
+                    legal_actions.push(Action::Pirate(pirate_id as u32, i_edge, Some(*player)))
+                }
+            }
+        }
+    }
 
     legal_actions
 }
 
+// whether a pirate blocks the edge between the two nodes
+pub fn is_pirated(game: &Game, nodes: (u32, u32)) -> bool {
+    match game.parameters.edge_map.get(&make_edge(nodes.0, nodes.1)) {
+        Some(i_edge) => game.round.board.v_pirates.contains(&(*i_edge as u32)),
+        None => false,
+    }
+}
+
+// friendly robber: players with few victory points cannot be robbed
+fn is_protected(game: &Game, i_player: u32) -> bool {
+    match game.parameters.friendly_robber {
+        Some(max_vp) => game.round.board.public_scores[i_player as usize] <= max_vp,
+        None => false,
+    }
+}
+
 fn get_legal_roads(game: &Game, mut legal_actions: Vec<Action>) -> Vec<Action> {
     
     let active_player: usize = game.round.active_player as usize;
@@ -402,7 +432,10 @@ fn get_legal_roads(game: &Game, mut legal_actions: Vec<Action>) -> Vec<Action> {
                             None => false,
                         };
 
-                        if !road_built { // if no road between first node and neighbor, new road is legal
+                        // roads run on land and not on top of ships
+                        let road_possible = game.parameters.is_land_edge(first_node.id, *n_id) && !has_ship(game, first_node.id, *n_id);
+
+                        if !road_built && road_possible { // if no road between first node and neighbor, new road is legal
                             legal_actions.push(Action::BuildRoad(first_node.id, *n_id))
                         }
                     },
@@ -418,6 +451,254 @@ fn get_legal_roads(game: &Game, mut legal_actions: Vec<Action>) -> Vec<Action> {
     legal_actions
 }
 
+fn has_ship(game: &Game, start_node: u32, end_node: u32) -> bool {
+    game.round.board.ships.iter().any(|ship| ship.is_on(start_node, end_node))
+}
+
+fn get_legal_ships(game: &Game, mut legal_actions: Vec<Action>) -> Vec<Action> {
+
+    let board = &game.round.board;
+    let active_player = game.round.active_player;
+    let budget = &board.budgets[active_player as usize];
+
+    let ship_affordable = budget.iter()
+                                .zip(&game.parameters.building_costs[4])
+                                .all(|(b, c)| b >= c);
+
+    if ship_affordable & (get_remaining_ships(active_player, board, game.parameters.max_ships) > 0) {
+        for (start_node, end_node) in get_ship_spots(game, None) {
+            legal_actions.push(Action::BuildShip(start_node, end_node));
+        }
+    }
+
+    // one ship per turn may be moved, but not during the special building phase
+    if (game.round.phase == Building) & !game.round.ship_moved {
+        for (i_ship, ship) in board.ships.iter().enumerate() {
+            if (ship.player != active_player) || (ship.turn == game.round.turn) || is_pirated(game, ship.nodes) || !is_open_ship(game, i_ship) {
+                continue
+            }
+
+            for (start_node, end_node) in get_ship_spots(game, Some(i_ship)) {
+                if !ship.is_on(start_node, end_node) {
+                    legal_actions.push(Action::MoveShip(ship.nodes.0, ship.nodes.1, start_node, end_node));
+                }
+            }
+        }
+    }
+
+    legal_actions
+}
+
+// free sea edges next to a settlement, city or ship of the active player (ignoring the ship which is moved)
+fn get_ship_spots(game: &Game, moved_ship: Option<usize>) -> Vec<(u32, u32)> {
+
+    let board = &game.round.board;
+    let active_player = game.round.active_player;
+
+    let ships: Vec<_> = board.ships.iter()
+                            .enumerate()
+                            .filter(|(i_ship, _)| Some(*i_ship) != moved_ship)
+                            .map(|(_, ship)| ship)
+                            .collect();
+
+    let mut spots: Vec<(u32, u32)> = vec![];
+
+    for node in &board.nodes {
+        let player_settled = (node.node_status == NodeStatus::Settled(active_player)) || (node.node_status == NodeStatus::Citied(active_player));
+
+        // a ship route cannot pass an opponent's building
+        let opponent_settled = matches!(node.node_status, NodeStatus::Settled(i_player) | NodeStatus::Citied(i_player) if i_player != active_player);
+        let player_ship = !opponent_settled && ships.iter().any(|ship| ship.player == active_player && ship.touches(node.id));
+
+        if !(player_settled || player_ship) {
+            continue
+        }
+
+        for neighbor in &node.neighbors {
+            if let Some(n_id) = neighbor {
+                let edge = make_edge(node.id, *n_id);
+
+                let road_built = match &node.roads {
+                    Some(v_roads) => v_roads.iter().any(|road| road.1 == *n_id),
+                    None => false,
+                };
+                let ship_built = ships.iter().any(|ship| ship.is_on(node.id, *n_id));
+
+                if game.parameters.is_sea_edge(node.id, *n_id) && !road_built && !ship_built && !is_pirated(game, edge) && !spots.contains(&edge) {
+                    spots.push(edge);
+                }
+            }
+        }
+    }
+
+    spots
+}
+
+// a ship may only be moved from the open end of a route: one of its nodes has neither a building of the player nor another of the player's ships
+fn is_open_ship(game: &Game, i_ship: usize) -> bool {
+
+    let board = &game.round.board;
+    let ship = &board.ships[i_ship];
+
+    [ship.nodes.0, ship.nodes.1].iter().any(|&i_node| {
+        let player_settled = (board.nodes[i_node as usize].node_status == NodeStatus::Settled(ship.player)) || (board.nodes[i_node as usize].node_status == NodeStatus::Citied(ship.player));
+        let other_ship = board.ships.iter()
+                            .enumerate()
+                            .any(|(j_ship, other)| (j_ship != i_ship) && (other.player == ship.player) && other.touches(i_node));
+
+        !player_settled && !other_ship
+    })
+}
+
+fn get_legal_builds(game: &Game, mut legal_actions: Vec<Action>) -> Vec<Action> {
+
+    let board = &game.round.board;
+    let active_player: usize = game.round.active_player as usize;
+    let budget = &game.round.board.budgets[active_player];
+
+    legal_actions.push(Action::NoBuying); // do not build
+
+    let building_costs = &game.parameters.building_costs;
+
+    let remaining_pieces = get_remaining_pieces(active_player as u32, board, &game.parameters.max_pieces);
+
+    // build road
+    let road_affordable = budget.iter()
+                                .zip(&building_costs[0])
+                                .all(|(b, c)| b >= c);
+
+    if road_affordable & (remaining_pieces[0] > 0) { // check affordability and remaining roads
+        legal_actions = get_legal_roads(game, legal_actions);
+    }
+
+    // build or move ships (only on boards with sea)
+    if game.parameters.has_sea() {
+        legal_actions = get_legal_ships(game, legal_actions);
+    }
+
+    // build settlement
+    let settlement_affordable = budget.iter()
+                                            .zip(&building_costs[1])
+                                            .all(|(b,c)| b >= c);
+
+    if settlement_affordable & (remaining_pieces[1] > 0) { // check affordability and remaining settlements
+        for node in &board.nodes {
+            if node.node_status == NodeStatus::Free && game.parameters.is_land_node(node.id) { // a new settlement only legal if node is free (not settled or adjacent to settled) and on land
+                let mut player_road = false;    // a new settlement needs to be attached to an existing road or ship
+                match &node.roads {         
+                    Some(v_roads) => {
+                        for (player, _) in v_roads {
+                            if active_player == *player as usize {
+                                player_road = true;
+                                break
+                            }
+                        }
+                    },
+                    None => (),
+                }
+
+                let player_ship = board.ships.iter().any(|ship| ship.player == active_player as u32 && ship.touches(node.id));
+
+                // knights block the node
+                let knight_present = board.knights.iter().any(|knight| knight.node == node.id);
+
+                if (player_road || player_ship) && !knight_present {
+                    legal_actions.push(Action::BuildSettlement(node.id));
+                }
+            }
+        }
+    }
+
+    // build city
+    let city_affordable = budget.iter()
+                                    .zip(&building_costs[2])
+                                    .all(|(b,c)| b >= c);
+    if city_affordable & (remaining_pieces[2] > 0) { // check affordability and remaining cities
+        for node in &board.nodes {
+            if node.node_status == NodeStatus::Settled(active_player as u32) { // new city only legal if the node has a settlement by the same player
+                legal_actions.push(Action::BuildCity(node.id));
+            }
+        }
+    }
+
+    // buy development card
+    let dev_card_affordable = budget.iter()
+                                    .zip(&building_costs[3])
+                                    .all(|(b,c)| b >= c);
+
+    let dev_card_available = board.get_n_undrawn_dev_cards() > 0;
+
+    if dev_card_affordable & dev_card_available { // check affordability and whether the deck is empty
+        legal_actions.push(Action::BuyDevCard);
+    }
+
+    // knights and city improvements (Cities & Knights only)
+    legal_actions = get_legal_cities_knights(game, legal_actions);
+
+    legal_actions
+}
+
+// pairs of free roads (road building cards): two legal roads, or one road twice if the player has only one road left
+pub fn get_free_road_pairs(game: &Game) -> Vec<(u32, u32, u32, u32)> {
+    let active_player = game.round.active_player;
+
+    let mut road_pairs: Vec<(u32, u32, u32, u32)> = vec![];
+
+    let n_remaining_roads = get_remaining_pieces(active_player, &game.round.board, &game.parameters.max_pieces)[0];
+
+    if n_remaining_roads == 0 {
+        return road_pairs
+    }
+
+    let mut legal_roads: Vec<Action> = vec![];
+    legal_roads = get_legal_roads(game, legal_roads);
+
+    // a single free road is given twice
+    if n_remaining_roads == 1 {
+        for road in &legal_roads {
+            match road {
+                Action::BuildRoad(f, s) => road_pairs.push((*f, *s, *f, *s)),
+                _ => (),
+            }
+        }
+
+        return road_pairs
+    }
+
+    for first_road in &legal_roads {
+        for second_road in &legal_roads {
+            if first_road != second_road {
+                let mut first_first_node: u32 = 0;
+                let mut first_second_node: u32 = 0;
+                match first_road {
+                    Action::BuildRoad(f,s) => {
+                        first_first_node = *f;
+                        first_second_node = *s;
+                    },
+                    _ => ()
+                }
+
+                let mut second_first_node: u32 = 0;
+                let mut second_second_node: u32 = 0;
+                match second_road {
+                    Action::BuildRoad(f,s) => {
+                        second_first_node = *f;
+                        second_second_node = *s;
+                    },
+                    _ => ()
+                }
+
+                // check whether the two roads are not the inverse of each other
+                if (first_first_node != second_second_node) & (first_second_node != second_first_node) {
+                    road_pairs.push((first_first_node, first_second_node, second_first_node, second_second_node));
+                }
+            }
+        }
+    }
+
+    road_pairs
+}
+
 fn get_legal_cards(game: &Game, mut legal_actions: Vec<Action>) -> Vec<Action> {
     
     legal_actions.push(Action::NoCardPlay);
@@ -425,21 +706,25 @@ fn get_legal_cards(game: &Game, mut legal_actions: Vec<Action>) -> Vec<Action> {
     let active_player: usize = game.round.active_player as usize;
 
     if game.round.cards_played < game.parameters.max_cards {
+        // cards bought during the current turn may not be played yet
         let private_card_deck: &Vec<u32> = &game.round.board.drawn_dev_cards[active_player].iter()
                                         .zip(&game.round.board.public_dev_cards[active_player])
-                                        .map(|(drawn, public)| drawn - public)
+                                        .zip(&game.round.board.dev_card_turns[active_player])
+                                        .map(|((drawn, public), turns)| {
+                                            let n_new = turns.iter().filter(|&&turn| turn == game.round.turn).count() as u32;
+                                            (drawn - public).saturating_sub(n_new)
+                                        })
                                         .collect();
 
         // let n_card_types = private_card_deck.len();
 
         for (card_type, stock) in private_card_deck.iter().enumerate() {
             if stock > &0 {
-                match card_type as u32 {
-                    0 => { // victory card
-                        legal_actions.push(Action::CardPlay(CardType::VPCard));
+                match card_type {
+                    VP_CARD => { // victory card (never played, revealed at the end of the game)
                     },
 
-                    1 => { // knight card
+                    KNIGHT_CARD => { // knight card
                         let mut robber_moves: Vec<Action> = vec![];
                         robber_moves = get_legal_robber_moves(game, robber_moves);
 
@@ -453,53 +738,28 @@ fn get_legal_cards(game: &Game, mut legal_actions: Vec<Action>) -> Vec<Action> {
                         }
                     },
 
-                    2 => { // road card (build two free roads)
-                        let mut legal_roads: Vec<Action> = vec![];
-                        legal_roads = get_legal_roads(game, legal_roads);
-
-                        for first_road in &legal_roads {
-                            for second_road in &legal_roads {
-                                if first_road != second_road {
-                                    let mut first_first_node: u32 = 0;
-                                    let mut first_second_node: u32 = 0;
-                                    match first_road {
-                                        Action::BuildRoad(f,s) => {
-                                            first_first_node = *f;
-                                            first_second_node = *s;
-                                        },
-                                        _ => ()
-                                    }
-
-                                    let mut second_first_node: u32 = 0;
-                                    let mut second_second_node: u32 = 0;
-                                    match second_road {
-                                        Action::BuildRoad(f,s) => {
-                                            second_first_node = *f;
-                                            second_second_node = *s;
-                                        },
-                                        _ => ()
-                                    }
-
-                                    // check whether the two roads are not the inverse of each other
-                                    if (first_first_node != second_second_node) & (first_second_node != second_first_node) {
-                                        legal_actions.push(Action::CardPlay(CardType::RoadsCard(first_first_node, first_second_node, second_first_node, second_second_node)));
-                                    }
-                                }
-                            }
-                            
+                    ROADS_CARD => { // road card (build two free roads, or one if only one road is left)
+                        for (s1, e1, s2, e2) in get_free_road_pairs(game) {
+                            legal_actions.push(Action::CardPlay(CardType::RoadsCard(s1, e1, s2, e2)));
                         }
                     },
 
-                    3 => { // year of plenty (2 resource cards)
-                        for first_resource in 0..game.parameters.n_players {
-                            for second_resource in 0..game.parameters.n_players {
-                                legal_actions.push(Action::CardPlay(CardType::PlentyCard(first_resource, second_resource)));
+                    PLENTY_CARD => { // year of plenty (2 resource cards, unordered, as long as the bank can supply them)
+                        let bank_stock = &game.round.board.bank_stock;
+
+                        for first_resource in 0..game.parameters.n_resources {
+                            for second_resource in first_resource..game.parameters.n_resources {
+                                let n_demanded = if first_resource == second_resource { 2 } else { 1 };
+
+                                if (bank_stock[first_resource as usize] >= n_demanded) & (bank_stock[second_resource as usize] >= n_demanded) {
+                                    legal_actions.push(Action::CardPlay(CardType::PlentyCard(first_resource, second_resource)));
+                                }
                             }
                         }
                     }
 
-                    4 => { // monopoly (1 resource)
-                        for resource in 0..game.parameters.n_players {
+                    MONOPOLY_CARD => { // monopoly (1 resource)
+                        for resource in 0..game.parameters.n_resources {
                             legal_actions.push(Action::CardPlay(CardType::MonopolyCard(resource)));
                         }
                     }
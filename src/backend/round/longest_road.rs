@@ -1,184 +1,125 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 
-use crate::backend::setup::road::Road;
+use crate::backend::setup::{knight::Knight, node::Node, node_status::NodeStatus, road::Road, ship::Ship};
 
+// a road needs at least this many segments to earn the longest road
+pub const MIN_LONGEST_ROAD: u32 = 5;
 
-pub fn get_longest_road(i_player: u32, roads: &Vec<Road>) -> u32 {
 
-    let mut player_roads: Vec<Road> = vec![];
-    for road in roads {
-        if road.player == i_player {
-            player_roads.push(road.clone())
-        }
-    }
+// length of the player's longest trail of roads and ships. A settlement, city or knight (Cities & Knights) of another player on a node cuts the road there.
+// a trail changes between roads and ships only at a settlement or city of the player (Seafarers)
+pub fn get_longest_road(i_player: u32, roads: &Vec<Road>, ships: &Vec<Ship>, nodes: &Vec<Node>, knights: &Vec<Knight>) -> u32 {
+
+    let player_segments: Vec<((u32, u32), bool)> = roads.iter().filter(|road| road.player == i_player).map(|road| (road.nodes, false))
+                                                    .chain(ships.iter().filter(|ship| ship.player == i_player).map(|ship| (ship.nodes, true)))
+                                                    .collect();
 
-    if player_roads.is_empty() {
+    if player_segments.is_empty() {
         return 0
-    } else if player_roads.len() == 1 {
-        return 1
-    } else {
-        find_players_longest_road(player_roads)
     }
-}
-
 
-// Function to find nodes that occur only once and roads containing those nodes
-fn find_unique_nodes_and_roads(roads: &[Road]) -> (Vec<u32>, Vec<Road>) {
-    let mut node_counts = HashMap::new();
-    let mut road_map = HashMap::new();
-
-    // Count occurrences of each node and record roads containing those nodes
-    for road in roads.iter().cloned() {
-        let (node1, node2) = road.nodes;
-        *node_counts.entry(node1).or_insert(0) += 1;
-        *node_counts.entry(node2).or_insert(0) += 1;
-        road_map.entry(node1).or_insert(Vec::new()).push(road.clone());
-        road_map.entry(node2).or_insert(Vec::new()).push(road);
+    // node -> (index of segment, node at the other end, segment is a ship)
+    let mut road_map: HashMap<u32, Vec<(usize, u32, bool)>> = HashMap::new();
+    for (i_segment, (segment_nodes, is_ship)) in player_segments.iter().enumerate() {
+        road_map.entry(segment_nodes.0).or_default().push((i_segment, segment_nodes.1, *is_ship));
+        road_map.entry(segment_nodes.1).or_default().push((i_segment, segment_nodes.0, *is_ship));
     }
 
-    // Find nodes that occur only once
-    let mut unique_nodes = Vec::new();
-    let mut unique_roads = HashSet::new();
-    for (node, count) in &node_counts {
-        if *count == 1 {
-            unique_nodes.push(*node);
-            if let Some(roads) = road_map.get(node) {
-                for road in roads {
-                    unique_roads.insert(road.clone());
-                }
-            }
-        } else {
-            continue
-        }
-    }
+    let mut used = vec![false; player_segments.len()];
 
-    (unique_nodes, unique_roads.into_iter().collect())
+    road_map.keys()
+        .map(|&start_node| extend_road(start_node, None, i_player, &road_map, nodes, knights, &mut used))
+        .max()
+        .unwrap_or(0)
 }
 
 
-struct ConnectedRoadIterator {
-    all_roads: Vec<Road>,
-    connected_roads: Vec<Vec<Road>>,
-    end_points: Vec<u32>,
-    unique_nodes: Vec<u32>,
-}
+// depth-first search over unused segments, coming in over a road or a ship (None at the start of the trail).
+// a trail may end at, but not pass through, an opponent's building.
+fn extend_road(
+    node: u32,
+    arrived_by_ship: Option<bool>,
+    i_player: u32,
+    road_map: &HashMap<u32, Vec<(usize, u32, bool)>>,
+    nodes: &Vec<Node>,
+    knights: &Vec<Knight>,
+    used: &mut Vec<bool>,
+) -> u32 {
 
-impl ConnectedRoadIterator {
-    fn new(all_roads: Vec<Road>, unique_road: Road) -> Self {
-        let connected_roads = vec![vec![unique_road.clone()]];
-        let end_points = vec![unique_road.nodes.0, unique_road.nodes.1];
-
-        let (unique_nodes, unique_roads) = find_unique_nodes_and_roads(&all_roads);
-
-        ConnectedRoadIterator {
-            all_roads,
-            connected_roads,
-            end_points,
-            unique_nodes,
-        }
+    if arrived_by_ship.is_some() && is_blocked(node, i_player, nodes, knights) {
+        return 0
     }
-}
-
-
-impl Iterator for ConnectedRoadIterator {
-    type Item = Vec<Vec<Road>>;
-
-    fn next(&mut self) -> Option<Self::Item> {
-
-        let init_len_connected_roads = self.connected_roads.iter().fold(0, |acc, x| acc.max(x.len()));
-
-        let mut new_connected_roads = self.connected_roads.clone();
-        
-        // println!("len. con roads: {:?}", new_connected_roads.len());
 
-        // TO DO: CHANGE THIS AD HOC CONSTRAINT TO SOMETHING JUSTIFIED
-        if new_connected_roads.len() > 2 * self.unique_nodes.len() {
-            return None;
-        };
+    let mut max_length = 0;
 
-        for connected_road in &self.connected_roads {
-
-            // find the remaining roads for a given set of connected roads
-            let remaining_roads: Vec<&Road> = self.all_roads.iter()
-                                            .filter(|road| !connected_road.iter().any(|r| r.nodes == road.nodes))
-                                            .collect();
-
-            // println!("{:?}",remaining_roads);
-
-            // if no roads remain, go to next connected road
-            if remaining_roads.is_empty() {
-                continue;
+    if let Some(connections) = road_map.get(&node) {
+        for &(i_segment, next_node, is_ship) in connections {
+            if used[i_segment] {
+                continue
             }
 
-            // find unique end points in this connected road
-            let (unique_nodes, unique_roads) = find_unique_nodes_and_roads(&connected_road);
-            // println!("unique nodes found");
+            // roads and ships only join at the player's own settlement or city
+            if matches!(arrived_by_ship, Some(by_ship) if by_ship != is_ship) && !is_own_building(node, i_player, nodes) {
+                continue
+            }
 
-            // println!("Unique nodes: {:?}", unique_nodes);
-            // if unique_nodes.is_empty() {
-            //     continue;
-            // }
+            used[i_segment] = true;
+            max_length = max_length.max(1 + extend_road(next_node, Some(is_ship), i_player, road_map, nodes, knights, used));
+            used[i_segment] = false;
+        }
+    }
 
-            // compare these unique points to the end points
-            let end_points = self.end_points.clone();
-            // println!("End points: {:?}", end_points);
-            for end_point in &end_points {
-                if unique_nodes.contains(&end_point) {
-                    for &remaining_road in &remaining_roads {
+    max_length
+}
 
-                        if &remaining_road.nodes.0 == end_point ||  &remaining_road.nodes.1 == end_point {
-                            let mut temp_road = connected_road.clone();
-                            temp_road.push(remaining_road.clone());
-                            new_connected_roads.push(temp_road);
+fn is_own_building(node: u32, i_player: u32, nodes: &Vec<Node>) -> bool {
+    match nodes.get(node as usize).map(|n| &n.node_status) {
+        Some(NodeStatus::Settled(owner)) | Some(NodeStatus::Citied(owner)) => *owner == i_player,
+        _ => false,
+    }
+}
 
-                            // remove the end point and add new one
-                            self.end_points.retain(|&x| x != end_point.clone());
+fn is_blocked(node: u32, i_player: u32, nodes: &Vec<Node>, knights: &Vec<Knight>) -> bool {
+    if knights.iter().any(|knight| knight.node == node && knight.player != i_player) {
+        return true
+    }
 
-                            if &remaining_road.nodes.0 == end_point {
-                                self.end_points.push(remaining_road.clone().nodes.1);
-                            } else {
-                                self.end_points.push(remaining_road.clone().nodes.0);
-                            }
+    match nodes.get(node as usize).map(|n| &n.node_status) {
+        Some(NodeStatus::Settled(owner)) => *owner != i_player,
+        Some(NodeStatus::Citied(owner)) => *owner != i_player,
+        _ => false,
+    }
+}
 
-                        }
 
-                    }
-                }
-            } 
+// the holder keeps the longest road on ties and loses it only when overtaken or when the road is cut below the minimum.
+// without a holder, the title goes to the unique longest road of at least MIN_LONGEST_ROAD segments (ties award nobody)
+pub fn update_longest_road(longest_roads: &Option<Vec<u32>>, prev_longest_road: &Option<(u32, u32)>) -> Option<(u32, u32)> {
 
-        }
+    let longest_roads = match longest_roads {
+        Some(lr) => lr,
+        None => return None,
+    };
 
+    let len_longest_road = longest_roads.iter().fold(0, |acc, &x| acc.max(x));
 
-        let final_len_connected_roads = new_connected_roads.iter().fold(0, |acc, x| acc.max(x.len()));
+    if let Some((i_holder, _)) = prev_longest_road {
+        let holder_road = longest_roads[*i_holder as usize];
 
-        // println!("ilcr: {:?}", init_len_connected_roads);
-        // println!("flcr: {:?}", final_len_connected_roads);
-        
-        if final_len_connected_roads <= init_len_connected_roads {
-            return None;
+        if holder_road >= MIN_LONGEST_ROAD && holder_road >= len_longest_road {
+            return Some((*i_holder, holder_road))
         }
-
-        self.connected_roads = new_connected_roads.clone();
-
-        Some(new_connected_roads)
-
     }
-}
-
-
-fn find_players_longest_road(roads: Vec<Road>) -> u32 {
-    // let (unique_nodes, mut unique_roads) = find_unique_nodes_and_roads(&roads);
 
-    // if unique_roads.is_empty() {
-    //     unique_roads.push(roads[1].clone())
-    // }
+    let longest: Vec<usize> = longest_roads.iter()
+                                .enumerate()
+                                .filter(|&(_, &length)| length == len_longest_road)
+                                .map(|(i_player, _)| i_player)
+                                .collect();
 
-    let mut max_lengths: Vec<usize> = vec![];
-    for road in &roads {
-        let iterator = ConnectedRoadIterator::new(roads.clone(), road.clone());
-        let last_iteration = iterator.last().unwrap_or(vec![vec![road.clone()]]);
-        max_lengths.push(last_iteration.iter().fold(0, |acc, x| acc.max(x.len())));
+    if len_longest_road >= MIN_LONGEST_ROAD && longest.len() == 1 {
+        Some((longest[0] as u32, len_longest_road))
+    } else {
+        None
     }
-    
-    max_lengths.iter().fold(0, |acc, x| acc.max(*x as u32))
-}
\ No newline at end of file
+}
@@ -1,8 +1,8 @@
 use serde::Deserialize;
 
-use crate::backend::setup::{game::Game, node_status::NodeStatus::{Citied, Settled}};
+use crate::backend::setup::{game::Game, node_status::NodeStatus::{Citied, Settled}, ruleset::Ruleset, terrain::Terrain};
 
-use super::action::Action;
+use super::{action::{move_robber, Action}, bank::{apply_shortage_rule, get_bank_stock}, cities_knights::{get_commodity, get_hand, N_COMMODITIES}};
 
 #[derive(Debug, Clone, Deserialize, PartialEq)]
 pub enum Phase {
@@ -14,6 +14,8 @@ pub enum Phase {
     TradingResponse,
     Building,
     SecondCardPhase,
+    SpecialBuilding,
+    GoldChoice,
     Terminal
 }
 
@@ -44,47 +46,50 @@ impl Game {
 
             },
 
-            Action::Robber(_, _, _) => {
+            Action::Robber(_, _, _) | Action::Pirate(_, _, _) => {
                 self.round.phase = Phase::FirstCardPhase;
                 self.round.active_player = self.round.throwing_player;
             },
 
-            Action::DiscardCards(_) => {
-                let n_players = &self.parameters.n_players;
-                let i_discard = &self.round.phase_count;
-
-                if i_discard < n_players {
-                    // move forward the active player (TO DO: Random sequence of discarding players?)
-                    self.round.active_player = (self.round.active_player + 1) % n_players;
-                    self.round.phase_count += 1;
-                } else {
-                    self.round.active_player = self.round.throwing_player;
-                    self.round.phase = Phase::RobberMove;
-                    self.round.phase_count = 0;
+            Action::DiscardCards(_) | Action::NoDiscard => {
+                // ask the next player over the hand limit, then hand the robber back to the thrower
+                let active_player = self.round.active_player;
+                self.round.discard_queue.retain(|&i_player| i_player != active_player);
+
+                match self.round.discard_queue.first() {
+                    Some(&i_player) => {
+                        self.round.active_player = i_player;
+                    },
+                    None => {
+                        self.round.active_player = self.round.throwing_player;
+                        self.round.phase = Phase::RobberMove;
+                        self.round.phase_count = 0;
+                    },
                 }
             },
 
-            Action::NoDiscard => {
-                let n_players = &self.parameters.n_players;
-                let i_discard = &self.round.phase_count;
-
-                if i_discard < n_players {
-                    // move forward the active player (TO DO: Random sequence of discarding players?)
-                    self.round.active_player = (self.round.active_player + 1) % n_players;
-                    self.round.phase_count += 1;
-                } else {
-                    self.round.active_player = self.round.throwing_player;
-                    self.round.phase = Phase::RobberMove;
-                    self.round.phase_count = 0;
+            Action::ChooseGold(_) => {
+                // ask the next player owed gold, then hand the turn back to the thrower
+                let active_player = self.round.active_player;
+                self.round.gold_queue.retain(|&i_player| i_player != active_player);
+
+                match self.round.gold_queue.first() {
+                    Some(&i_player) => {
+                        self.round.active_player = i_player;
+                    },
+                    None => {
+                        self.round.active_player = self.round.throwing_player;
+                        self.round.phase = Phase::FirstCardPhase;
+                    },
                 }
             },
 
             Action::NoCardPlay => {
                 let phase = &self.round.phase;
 
-                // throw dice if end of second card phase has been reached
+                // end the turn if end of second card phase has been reached
                 if phase == &Phase::SecondCardPhase {
-                    self.throw_dice();
+                    self.end_turn();
                 } else {
                     self.round.phase = Phase::TradingQuote;
                     self.round.phase_count = 0;
@@ -96,9 +101,9 @@ impl Game {
 
                 self.round.card_count += 1;
 
-                // throw dice if end of second card phase has been reached
+                // end the turn if end of second card phase has been reached
                 if phase == &Phase::SecondCardPhase {
-                    self.throw_dice();
+                    self.end_turn();
                 }
             },
 
@@ -113,7 +118,7 @@ impl Game {
                 self.round.active_player = self.round.throwing_player;
             },
 
-            Action::HarborTrade(_,_,_) => {
+            Action::HarborTrade(_,_,_) | Action::CommodityTrade(_, _) => {
                 self.round.phase_count = ((&self.round.phase_count / self.parameters.n_players) + 1) * self.parameters.n_players;
 
                 self.round.active_player = self.round.throwing_player;
@@ -140,14 +145,26 @@ impl Game {
             },
 
             Action::NoBuying => {
-                self.round.phase = Phase::SecondCardPhase;
-                self.round.phase_count = 0;
+                if self.round.phase == Phase::SpecialBuilding {
+                    self.iterate_special_building();
+                } else {
+                    self.round.phase = Phase::SecondCardPhase;
+                    self.round.phase_count = 0;
+                }
             },
 
             Action::BuildRoad(_, _) => {
                 self.iterate_building();
             },
 
+            Action::BuildShip(_, _) => {
+                self.iterate_building();
+            },
+
+            Action::MoveShip(_, _, _, _) => {
+                self.iterate_building();
+            },
+
             Action::BuildSettlement(_) => {
                 self.iterate_building();
             },
@@ -156,6 +173,10 @@ impl Game {
                 self.iterate_building();
             },
 
+            Action::BuildKnight(_) | Action::ActivateKnight(_) | Action::PromoteKnight(_) | Action::BuildImprovement(_) | Action::PlayProgressCard(_) => {
+                self.iterate_building();
+            },
+
             Action::BuyDevCard => {
                 self.iterate_building();
             },
@@ -184,6 +205,12 @@ impl Game {
         if let Some(d) = dice_outcome {
                 let tiles = &self.round.board.tiles;
 
+                // collect what every player is owed before paying out of the bank
+                let mut payouts = vec![vec![0_u32; self.parameters.n_resources as usize]; self.parameters.n_players as usize];
+                let mut gold_owed = vec![0_u32; self.parameters.n_players as usize];
+                let mut commodities = vec![vec![0_u32; N_COMMODITIES]; self.parameters.n_players as usize];
+                let cities_knights = self.parameters.ruleset == Ruleset::CitiesAndKnights;
+
                 for tile in tiles {
                     let tile_rng = match tile.rng {
                         Some(tr) => tr,
@@ -192,48 +219,93 @@ impl Game {
 
                     if tile_rng == d {
 
-                        match tile.resource {
-                            Some(i_resource) => {
+                        match Terrain::from_resource(tile.resource, self.parameters.n_resources) {
+                            Terrain::Land(i_resource) => {
                                 let nodes = &tile.nodes;
 
                             for i_node in nodes {
 
                                 match &self.round.board.nodes[*i_node as usize].node_status {
                                     Settled(i_player) => {
-                                        self.round.board.budgets[*i_player as usize][i_resource as usize] += 1;
-                                        self.round.board.public_budgets[*i_player as usize][i_resource as usize] += 1;
-                                        self.round.board.total_drawn_resources[*i_player as usize][i_resource as usize] += 1;
+                                        payouts[*i_player as usize][i_resource as usize] += 1;
                                     },
                                     Citied(i_player) => {
-                                        self.round.board.budgets[*i_player as usize][i_resource as usize] += 2;
-                                        self.round.board.total_drawn_resources[*i_player as usize][i_resource as usize] += 2;
+                                        // with Cities & Knights, cities on forests, pastures and mountains trade their second resource for a commodity
+                                        match get_commodity(i_resource) {
+                                            Some(i_commodity) if cities_knights => {
+                                                payouts[*i_player as usize][i_resource as usize] += 1;
+                                                commodities[*i_player as usize][i_commodity] += 1;
+                                            },
+                                            _ => {
+                                                payouts[*i_player as usize][i_resource as usize] += 2;
+                                            },
+                                        }
                                     },
                                     _ => continue
                                 }
                             }
                             },
-                            None => continue,
+                            Terrain::Gold => {
+                                // gold fields pay resources of choice, which are chosen after the production
+                                for i_node in &tile.nodes {
+                                    match &self.round.board.nodes[*i_node as usize].node_status {
+                                        Settled(i_player) => {
+                                            gold_owed[*i_player as usize] += 1;
+                                        },
+                                        Citied(i_player) => {
+                                            gold_owed[*i_player as usize] += 2;
+                                        },
+                                        _ => continue
+                                    }
+                                }
+                            },
+                            _ => continue,
                         }
                         
                     }
                 }
+
+                let bank_stock = get_bank_stock(&self.round.board.budgets, &self.parameters.resource_supply);
+                let payouts = apply_shortage_rule(&payouts, &bank_stock);
+
+                for (i_player, payout) in payouts.iter().enumerate() {
+                    for (i_resource, n_cards) in payout.iter().enumerate() {
+                        self.round.board.budgets[i_player][i_resource] += n_cards;
+                        self.round.board.public_budgets[i_player][i_resource] += n_cards;
+                        self.round.board.total_drawn_resources[i_player][i_resource] += n_cards;
+                    }
+                }
+
+                for (i_player, produced) in commodities.iter().enumerate() {
+                    for (i_commodity, n_cards) in produced.iter().enumerate() {
+                        self.round.board.commodities[i_player][i_commodity] += n_cards;
+                    }
+                }
+
+                self.round.gold_owed = gold_owed;
             
         }
+
+        self.round.board.bank_stock = get_bank_stock(&self.round.board.budgets, &self.parameters.resource_supply);
     
     }
 
 
     fn throw_dice(&mut self) {
         // throw dice
-        self.round.board.dice = self.round.board.dice.clone().throw();
-        self.round.board.dice_outcome = self.round.board.dice.draw;
+        self.round.board.dice = self.round.board.dice.clone().throw(&mut self.round.board.rng);
+        self.round.board.dice_outcome = self.round.board.dice.draw();
         // println!("{:#?}",self.round.throwing_player);
         // println!("Phase: {:#?}", self.round.phase);
 
 
+        // every throw of dice starts a new turn
+        self.round.turn += 1;
+
         // reset the phase counters to zero after throw of dice
         self.round.phase_count = 0;
         self.round.cards_played = 0;
+        self.round.ship_moved = false;
 
         // After SetUp, reset the active player to 0. Otherwise, iterate active player by one.
         match &self.round.phase {
@@ -247,6 +319,15 @@ impl Game {
 
         self.round.active_player = self.round.throwing_player.clone();
 
+        if self.parameters.robber_returns_to_desert {
+            self.return_robbers_to_desert();
+        }
+
+        // the event die is resolved before the production (Cities & Knights)
+        if self.parameters.ruleset == Ruleset::CitiesAndKnights {
+            self.resolve_event_die();
+        }
+
         // advance phase based on dice outcome
         let mut matched_robber_no = false;
         match self.round.board.dice_outcome {
@@ -264,14 +345,71 @@ impl Game {
             None => (),
         }
 
-        if !matched_robber_no {
+        if matched_robber_no {
+            self.start_discards();
+        } else {
             self.produce_resources();
-            self.round.phase = Phase::FirstCardPhase;
+            self.start_gold_choices();
         }
 
         
     }
 
+    // only the players over the hand limit discard (in seat order starting with the thrower)
+    fn start_discards(&mut self) {
+        let n_players = self.parameters.n_players;
+
+        self.round.discard_queue = (0..n_players)
+            .map(|i_seat| (self.round.throwing_player + i_seat) % n_players)
+            .filter(|&i_player| get_hand(self, i_player).iter().sum::<u32>() > self.parameters.hand_limit)
+            .collect();
+        self.hidden.pending_discards = vec![];
+
+        match self.round.discard_queue.first() {
+            Some(&i_player) => {
+                self.round.active_player = i_player;
+            },
+            None => {
+                self.round.phase = Phase::RobberMove;
+                self.round.phase_count = 0;
+            },
+        }
+    }
+
+    // players owed resources from gold fields choose them (in seat order starting with the thrower)
+    fn start_gold_choices(&mut self) {
+        let n_players = self.parameters.n_players;
+
+        self.round.gold_queue = (0..n_players)
+            .map(|i_seat| (self.round.throwing_player + i_seat) % n_players)
+            .filter(|&i_player| self.round.gold_owed[i_player as usize] > 0)
+            .collect();
+
+        match self.round.gold_queue.first() {
+            Some(&i_player) => {
+                self.round.active_player = i_player;
+                self.round.phase = Phase::GoldChoice;
+            },
+            None => {
+                self.round.phase = Phase::FirstCardPhase;
+            },
+        }
+    }
+
+    fn return_robbers_to_desert(&mut self) {
+        let i_desert = match (0..self.round.board.tile_rng.len()).position(|i_tile| self.round.board.tile_rng[i_tile].is_none() && self.parameters.get_terrain(i_tile) != Terrain::Sea) {
+            Some(i_tile) => i_tile,
+            None => return,
+        };
+
+        for i_robber in 0..self.round.board.v_robbers.len() {
+            if self.round.board.robber_players[i_robber] == Some(self.round.throwing_player) {
+                move_robber(self, i_robber, i_desert as u32);
+                self.round.board.robber_players[i_robber] = None;
+            }
+        }
+    }
+
     fn iterate_trade(&mut self) {
         let count_trades = &self.round.phase_count / self.parameters.n_players;
         // println!("{}", count_trades);
@@ -303,7 +441,11 @@ impl Game {
     fn iterate_building(&mut self) {
         self.round.phase_count += 1;
 
-        if self.round.phase_count < self.parameters.max_builds {
+        if self.round.phase == Phase::SpecialBuilding {
+            if self.round.phase_count >= self.parameters.max_builds {
+                self.iterate_special_building();
+            }
+        } else if self.round.phase_count < self.parameters.max_builds {
             self.round.phase = Phase::Building
         } else {
             self.round.phase = Phase::SecondCardPhase;
@@ -311,4 +453,25 @@ impl Game {
         }
     }
 
+    // with the special building phase, every other player may build before the next dice throw
+    fn end_turn(&mut self) {
+        if self.parameters.special_building_phase {
+            self.round.phase = Phase::SpecialBuilding;
+            self.round.phase_count = 0;
+            self.round.active_player = (self.round.throwing_player + 1) % self.parameters.n_players;
+        } else {
+            self.throw_dice();
+        }
+    }
+
+    fn iterate_special_building(&mut self) {
+        self.round.phase_count = 0;
+        self.round.active_player = (self.round.active_player + 1) % self.parameters.n_players;
+
+        // throw dice once all other players had their chance to build
+        if self.round.active_player == self.round.throwing_player {
+            self.throw_dice();
+        }
+    }
+
 }
\ No newline at end of file
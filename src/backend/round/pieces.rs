@@ -0,0 +1,30 @@
+use crate::backend::setup::{board::Board, node_status::NodeStatus};
+
+// order of the building costs (as named in costs.csv)
+pub const BUILDING_NAMES: [&str; 5] = ["road", "settlement", "city", "dev_card", "ship"];
+
+// number of roads, settlements and cities the player can still place (same order as the building costs).
+// a settlement that is upgraded to a city returns to the player's supply
+pub fn get_remaining_pieces(i_player: u32, board: &Board, max_pieces: &Vec<u32>) -> Vec<u32> {
+
+    let n_roads = match &board.roads {
+        Some(v_roads) => v_roads.iter().filter(|road| road.player == i_player).count() as u32,
+        None => 0,
+    };
+
+    let n_settlements = board.nodes.iter().filter(|node| node.node_status == NodeStatus::Settled(i_player)).count() as u32;
+    let n_cities = board.nodes.iter().filter(|node| node.node_status == NodeStatus::Citied(i_player)).count() as u32;
+
+    vec![
+        max_pieces[0].saturating_sub(n_roads),
+        max_pieces[1].saturating_sub(n_settlements),
+        max_pieces[2].saturating_sub(n_cities),
+    ]
+}
+
+// number of ships the player can still build
+pub fn get_remaining_ships(i_player: u32, board: &Board, max_ships: u32) -> u32 {
+    let n_ships = board.ships.iter().filter(|ship| ship.player == i_player).count() as u32;
+
+    max_ships.saturating_sub(n_ships)
+}
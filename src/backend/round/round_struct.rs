@@ -9,11 +9,19 @@ pub struct Round {
     pub board: Board, 
     pub active_player: u32,
     pub throwing_player: u32,
+    pub turn: u32,
     pub cards_played: u32,
     pub phase: Phase,
     pub phase_count: u32,
     pub card_count: u32,
     pub robber_count: u32,
+    // players who still have to discard (their choices are hidden until everyone has discarded)
+    pub discard_queue: Vec<u32>,
+    // players who still have to choose resources for gold fields, and how many each of them is owed
+    pub gold_queue: Vec<u32>,
+    pub gold_owed: Vec<u32>,
+    // a player may move one ship per turn
+    pub ship_moved: bool,
     pub action: Option<Action>,
     pub outcome: Option<Outcome>,
 }
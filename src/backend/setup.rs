@@ -1,17 +1,27 @@
 pub mod game;
+pub mod hidden_state;
 pub mod game_parameters;
 pub mod shape;
 
 pub mod dice;
+pub mod event_deck;
+pub mod dice_source;
+pub mod rng_service;
 
 pub mod board;
 pub mod tile;
+pub mod terrain;
 pub mod node;
 pub mod harbor;
+pub mod map_generator;
+pub mod setup_payout;
+pub mod ruleset;
 
 pub mod road;
+pub mod ship;
 pub mod settlement;
 pub mod city;
+pub mod knight;
 pub mod node_status;
 
 pub mod player;
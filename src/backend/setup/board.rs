@@ -1,9 +1,9 @@
 
 use serde::Deserialize;
 
-use crate::backend::{round::{longest_road::get_longest_road, score::{get_public_score, get_score}}, setup::shape::get_n_tiles};
+use crate::backend::{round::{bank::get_bank_stock, cities_knights::{N_COMMODITIES, N_PROGRESS_CARD_TYPES, PROGRESS_DECKS}, largest_army::update_largest_army, longest_road::{get_longest_road, update_longest_road}, score::{get_public_score, get_score}}, setup::shape::get_n_tiles};
 
-use super::{dice::Dice, game_parameters::GameParameters, harbor::Harbor, node::Node, road::Road, shape::get_n_node, tile::Tile};
+use super::{dice::Dice, dice_source::DiceSource, game_parameters::GameParameters, harbor::Harbor, knight::Knight, node::Node, node_status::NodeStatus, rng_service::RngService, road::Road, ruleset::Ruleset, shape::get_n_node, ship::Ship, terrain::Terrain, tile::Tile};
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct Board {
@@ -11,8 +11,13 @@ pub struct Board {
     pub tiles: Vec<Tile>,
 
     pub v_robbers: Vec<u32>,
+    // player who last moved each robber
+    pub robber_players: Vec<Option<u32>>,
+    // coastal edges blocked by a pirate
+    pub v_pirates: Vec<u32>,
 
     pub roads: Option<Vec<Road>>,
+    pub ships: Vec<Ship>,
     pub harbors: Option<Vec<Harbor>>,
 
     pub tile_rng: Vec<Option<u32>>,
@@ -20,23 +25,49 @@ pub struct Board {
     pub budgets: Vec<Vec<u32>>,
     pub public_budgets: Vec<Vec<u32>>,
     pub total_drawn_resources: Vec<Vec<u32>>,
+    pub bank_stock: Vec<u32>,
 
-    pub undrawn_dev_cards: Vec<u32>,
+    // the order of the development card deck is hidden, the number of cards left is public
+    pub n_undrawn_dev_cards: u32,
     pub drawn_dev_cards: Vec<Vec<u32>>,
     pub public_dev_cards: Vec<Vec<u32>>,
     pub unknown_dev_cards: Vec<Vec<Vec<f64>>>,
+    pub dev_card_turns: Vec<Vec<Vec<u32>>>,
 
     pub longest_roads: Option<Vec<u32>>,
     pub prev_longest_road: Option<(u32, u32)>,
     pub armies: Vec<u32>,
     pub prev_largest_army: Option<(u32, u32)>,
 
+    // island of every node (computed once, the tiles do not change during a game)
+    pub node_islands: Vec<Option<usize>>,
+    // islands settled during the setup, and the islands every player has discovered since
+    pub home_islands: Vec<usize>,
+    pub discovered_islands: Vec<Vec<usize>>,
+
+    // Cities & Knights (empty in the base game)
+    pub commodities: Vec<Vec<u32>>,
+    pub improvements: Vec<Vec<u32>>,
+    pub metropolises: Vec<Option<u32>>,
+    pub knights: Vec<Knight>,
+    pub barbarian_position: u32,
+    pub defender_points: Vec<u32>,
+    // progress cards held by every player (by type), victory points of revealed progress cards,
+    // and the number of cards left in every track's deck (its order is hidden)
+    pub progress_cards: Vec<Vec<u32>>,
+    pub progress_points: Vec<u32>,
+    pub progress_deck: Vec<u32>,
+    pub event_dice: Dice,
+
     pub scores: Vec<u32>,
     pub public_scores: Vec<u32>,
     pub unknown_scores: Vec<Vec<f64>>,
 
-    pub dice: Dice,
+    pub dice: DiceSource,
     pub dice_outcome: Option<u32>,
+
+    // seeded random streams of dice, events, steals, development cards and AI seats
+    pub rng: RngService,
 }
 
 impl Board {
@@ -70,10 +101,10 @@ impl Board {
             Some(robbers) => {
                 robbers.clone()
             },
-            None => { // set robbers on first tile with rng = None
+            None => { // set robbers on first land tile with rng = None
                 let mut first_free_tile = 0_u32;
                 for (tile, resource) in parameters.init_tile_rng.iter().enumerate() {
-                    if *resource == None {
+                    if *resource == None && parameters.get_terrain(tile) != Terrain::Sea {
                         first_free_tile = tile as u32;
                         break;
                     }
@@ -82,6 +113,14 @@ impl Board {
             },
         };
 
+        let robber_players: Vec<Option<u32>> = vec![None; v_robbers.len()];
+
+        // initialize pirates
+        let v_pirates = match &parameters.init_v_pirate {
+            Some(pirates) => pirates.clone(),
+            None => vec![],
+        };
+
         // initialize nodes
         let mut nodes = vec![Node::default(); n_nodes as usize];
 
@@ -202,14 +241,11 @@ impl Board {
             }
         }
 
-        // initialize previously longest road (from init roads)
-        let prev_longest_road: Option<(u32, u32)> = parameters.init_longest_road;
-
-        // initialize longest roads
+        // initialize longest roads (there are no ships or knights before the game)
         let longest_roads: Option<Vec<u32>> = match &roads {
             Some(v_roads) => {
                 let v_longest = (0..parameters.n_players)
-                                    .map(|i_player| get_longest_road(i_player, v_roads)).collect();
+                                    .map(|i_player| get_longest_road(i_player, v_roads, &vec![], &nodes, &vec![])).collect();
                 Some(v_longest)
             },
             None => {
@@ -217,22 +253,50 @@ impl Board {
             },
         };
 
-        // initialize previously largest army
-        let prev_largest_army = parameters.init_largest_army.clone();
+        // islands settled in the template are home islands
+        let node_islands = parameters.get_node_islands();
+        let mut home_islands: Vec<usize> = vec![];
+        for node in &nodes {
+            if matches!(node.node_status, NodeStatus::Settled(_) | NodeStatus::Citied(_)) {
+                if let Some(i_island) = node_islands[node.id as usize] {
+                    if !home_islands.contains(&i_island) {
+                        home_islands.push(i_island);
+                    }
+                }
+            }
+        }
+
+        // initialize the bank (whatever the players do not hold)
+        let bank_stock = get_bank_stock(&budgets, &parameters.resource_supply);
+
+        // initialize turns on which development cards were bought (cards of the template count as bought before the game)
+        let dev_card_turns: Vec<Vec<Vec<u32>>> = vec![vec![vec![]; parameters.n_dev_card_types as usize]; parameters.n_players as usize];
 
-        // initialize largest army
+        // initialize previously longest road (derived from the roads if not given)
+        let prev_longest_road: Option<(u32, u32)> = match &parameters.init_longest_road {
+            Some(plr) => Some(*plr),
+            None => update_longest_road(&longest_roads, &None),
+        };
+
+        // initialize largest army (played knights)
         let armies: Vec<u32> = public_dev_cards.iter().map(|v_player| v_player[1]).collect();
 
+        // initialize previously largest army (derived from the armies if not given)
+        let prev_largest_army = match &parameters.init_largest_army {
+            Some(pla) => Some(*pla),
+            None => update_largest_army(&armies, &None),
+        };
+
 
         // println!("{:?}", parameters.n_players);
         let scores: Vec<u32> = (0..parameters.n_players)
-                                .map(|i_player| get_score(i_player, &nodes,  &drawn_dev_cards, &longest_roads, &prev_longest_road,
-                                &armies, &prev_largest_army))
+                                .map(|i_player| get_score(i_player, &nodes,  &drawn_dev_cards, &prev_longest_road,
+                                &prev_largest_army))
                                 .collect();
 
         let public_scores: Vec<u32> = (0..parameters.n_players)
-                                .map(|i_player| get_public_score(i_player, &nodes, &public_dev_cards, &longest_roads, &prev_longest_road,
-                                &armies, &prev_largest_army))
+                                .map(|i_player| get_public_score(i_player, &nodes, &public_dev_cards, &prev_longest_road,
+                                &prev_largest_army))
                                 .collect();     
 
         // initialize unknown score
@@ -247,7 +311,16 @@ impl Board {
         }  
 
 
-        let dice = Dice::new(parameters.n_dice, parameters.n_faces, parameters.dice_seed);                     
+        let dice = DiceSource::new(parameters);
+
+        let rng = RngService::new(parameters);
+        let n_undrawn_dev_cards = parameters.init_undrawn_dev_cards.iter().sum();
+
+        // progress decks (Cities & Knights)
+        let progress_deck: Vec<u32> = match parameters.ruleset {
+            Ruleset::CitiesAndKnights => PROGRESS_DECKS.iter().map(|n_cards| n_cards.iter().sum()).collect(),
+            Ruleset::Base => vec![0; N_COMMODITIES],
+        };
         
 
         Ok(Self {
@@ -255,8 +328,11 @@ impl Board {
             tiles,
 
             v_robbers,
+            robber_players,
+            v_pirates,
 
             roads: roads.clone(),
+            ships: vec![],
             harbors: harbors.clone(),
 
             tile_rng: parameters.init_tile_rng.clone(),
@@ -264,11 +340,13 @@ impl Board {
             budgets,
             public_budgets,
             total_drawn_resources,
+            bank_stock,
 
-            undrawn_dev_cards: parameters.init_undrawn_dev_cards.clone(),
+            n_undrawn_dev_cards,
             drawn_dev_cards,
             public_dev_cards,
             unknown_dev_cards,
+            dev_card_turns,
 
             longest_roads,
             armies,
@@ -278,10 +356,31 @@ impl Board {
             unknown_scores,
             
             dice: dice.clone(),
-            dice_outcome: dice.draw,
+            dice_outcome: dice.draw(),
             prev_longest_road,
             prev_largest_army,    
+
+            node_islands,
+            home_islands,
+            discovered_islands: vec![vec![]; parameters.n_players as usize],
+
+            commodities: vec![vec![0; N_COMMODITIES]; parameters.n_players as usize],
+            improvements: vec![vec![0; N_COMMODITIES]; parameters.n_players as usize],
+            metropolises: vec![None; N_COMMODITIES],
+            knights: vec![],
+            barbarian_position: 0,
+            defender_points: vec![0; parameters.n_players as usize],
+            progress_cards: vec![vec![0; N_PROGRESS_CARD_TYPES]; parameters.n_players as usize],
+            progress_points: vec![0; parameters.n_players as usize],
+            progress_deck,
+            event_dice: Dice::new(1, 6),
+
+            rng,
         })
     }
 
-}
\ No newline at end of file
+    pub fn get_n_undrawn_dev_cards(&self) -> u32 {
+        self.n_undrawn_dev_cards
+    }
+
+}
@@ -1,4 +1,4 @@
-use rand::{Rng, SeedableRng};
+use rand::Rng;
 use rand::rngs::StdRng;
 use serde::Deserialize;
 
@@ -6,37 +6,35 @@ use serde::Deserialize;
 pub struct Dice {
     n_dice: u32,
     n_faces: u32,
-    
-    seed: u64,
 
     n_throws: u32,
 
     pub draw: Option<u32>,
+    // value of every single die of the last throw
+    pub faces: Vec<u32>,
 }
 
 
 impl Dice {
-    pub fn new(n_dice: u32, n_faces: u32, seed: u64) -> Self {
-        Dice {n_dice, n_faces, seed, n_throws: 0, draw: None}
+    pub fn new(n_dice: u32, n_faces: u32) -> Self {
+        Dice {n_dice, n_faces, n_throws: 0, draw: None, faces: vec![]}
     }
 
-    pub fn throw(mut self) -> Self {
+    // the generator is the next draw of the dice (or events) stream of the RNG service
+    pub fn throw(self, mut rng: StdRng) -> Self {
 
         let n_dice = self.n_dice;
         let n_faces = self.n_faces;
 
-        let seed = self.seed;
-
-        let mut rng = StdRng::seed_from_u64(seed);
-
-        let draw: u32 = (0..n_dice).map(|_| rng.gen_range(1..=n_faces)).sum();
+        let faces: Vec<u32> = (0..n_dice).map(|_| rng.gen_range(1..=n_faces)).collect();
+        let draw: u32 = faces.iter().sum();
 
         Dice {
             n_dice,
             n_faces,
-            seed: seed + 1,
             n_throws: self.n_throws,
             draw: Some(draw),
+            faces,
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,48 @@
+use serde::Deserialize;
+
+use super::{dice::Dice, event_deck::EventDeck, game_parameters::GameParameters, rng_service::{RngService, Stream}};
+
+// how the production numbers are drawn
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiceMode {
+    Dice,
+    EventDeck,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub enum DiceSource {
+    Dice(Dice),
+    EventDeck(EventDeck),
+}
+
+
+impl DiceSource {
+    pub fn new(parameters: &GameParameters) -> Self {
+        match parameters.dice_mode {
+            DiceMode::Dice => DiceSource::Dice(Dice::new(parameters.n_dice, parameters.n_faces)),
+            DiceMode::EventDeck => DiceSource::EventDeck(EventDeck::new(parameters.n_dice, parameters.n_faces, parameters.event_deck_reshuffle)),
+        }
+    }
+
+    pub fn throw(self, rng: &mut RngService) -> Self {
+        match self {
+            DiceSource::Dice(dice) => DiceSource::Dice(dice.throw(rng.rng(Stream::Dice))),
+            DiceSource::EventDeck(deck) => DiceSource::EventDeck(deck.throw(rng)),
+        }
+    }
+
+    pub fn draw(&self) -> Option<u32> {
+        match self {
+            DiceSource::Dice(dice) => dice.draw,
+            DiceSource::EventDeck(deck) => deck.draw,
+        }
+    }
+
+    // value of every single die of the last throw (the first one is the red die)
+    pub fn faces(&self) -> &Vec<u32> {
+        match self {
+            DiceSource::Dice(dice) => &dice.faces,
+            DiceSource::EventDeck(deck) => &deck.faces,
+        }
+    }
+}
@@ -37,4 +37,11 @@ pub fn edge_index_map(edges: &Vec<(u32, u32)>) -> HashMap<(u32, u32), usize> {
         edge_map.insert(edge, index);
     }
     edge_map
+}
+
+// Function to get the nodes of an edge from its index
+pub fn get_edge_nodes(edge_map: &HashMap<(u32, u32), usize>, i_edge: usize) -> Option<(u32, u32)> {
+    edge_map.iter()
+        .find(|(_, &index)| index == i_edge)
+        .map(|(&edge, _)| edge)
 }
\ No newline at end of file
@@ -0,0 +1,58 @@
+use rand::seq::SliceRandom;
+use serde::Deserialize;
+
+use super::rng_service::{RngService, Stream};
+
+// a deck with one card for every combination of dice faces (36 cards for two six-sided dice).
+// the dice distribution holds exactly over the deck, which is reshuffled once only reshuffle_at cards are left
+#[derive(Debug, Clone, Deserialize)]
+pub struct EventDeck {
+    n_dice: u32,
+    n_faces: u32,
+
+    reshuffle_at: u32,
+
+    // undrawn cards (the top card is the last one)
+    cards: Vec<Vec<u32>>,
+    pub n_reshuffles: u32,
+
+    pub draw: Option<u32>,
+    pub faces: Vec<u32>,
+}
+
+
+impl EventDeck {
+    pub fn new(n_dice: u32, n_faces: u32, reshuffle_at: u32) -> Self {
+        EventDeck {n_dice, n_faces, reshuffle_at, cards: vec![], n_reshuffles: 0, draw: None, faces: vec![]}
+    }
+
+    // only reshuffles draw from the dice stream
+    pub fn throw(mut self, rng: &mut RngService) -> Self {
+        if self.cards.len() as u32 <= self.reshuffle_at {
+            self.cards = self.get_shuffled_deck(rng);
+            self.n_reshuffles += 1;
+        }
+
+        let faces = self.cards.pop().expect("The event deck is empty.");
+        let draw: u32 = faces.iter().sum();
+
+        EventDeck {
+            draw: Some(draw),
+            faces,
+            ..self
+        }
+    }
+
+    fn get_shuffled_deck(&self, rng: &mut RngService) -> Vec<Vec<u32>> {
+        let n_cards = self.n_faces.pow(self.n_dice);
+
+        // card i shows the digits of i in base n_faces
+        let mut deck: Vec<Vec<u32>> = (0..n_cards)
+            .map(|i_card| (0..self.n_dice).map(|i_die| (i_card / self.n_faces.pow(i_die)) % self.n_faces + 1).collect())
+            .collect();
+
+        deck.shuffle(&mut rng.rng(Stream::Dice));
+
+        deck
+    }
+}
@@ -1,9 +1,9 @@
 
-use std::{io::{self, Error}, thread::sleep, time::{Duration, Instant, SystemTime}};
+use std::{io, thread::sleep, time::{Duration, Instant, SystemTime}};
 
-use crate::{backend::{logging::log_entry, round::{action::Action, legal, phase::Phase, round_struct::Round}}, frontend::{actions::get_pretty_action, board_parameters::UIBoardParameters}};
+use crate::{ai::simple_nn::encode::fits_network, backend::{logging::log_entry, round::{action::Action, legal, phase::Phase, round_struct::Round}}, frontend::{actions::get_pretty_action, board_parameters::UIBoardParameters}};
 
-use super::{super::logging::{log_entry::LogEntry, summary::Summary}, board::Board, game_parameters::GameParameters, player::PlayerType};
+use super::{super::logging::{log_entry::LogEntry, summary::Summary}, board::Board, game_parameters::GameParameters, hidden_state::HiddenState, player::PlayerType};
 
 #[derive(Debug, Clone)]
 pub struct Game {
@@ -11,6 +11,7 @@ pub struct Game {
     pub round: Round,
     pub log: Vec<LogEntry>,
     pub result: Option<Summary>,
+    pub(in crate::backend) hidden: HiddenState,
 }
 
 impl Default for Game {
@@ -25,8 +26,11 @@ impl Default for Game {
 }
 
 impl Game {
-    pub fn new(parameters: GameParameters) -> Result<Self, Error> {
-        unimplemented!()
+    pub fn new(parameters: GameParameters) -> Result<Self, &'static str> {
+
+        let start = SystemTime::now();
+
+        Self::initialize_from_parameters(parameters, start)
     }
 
     pub fn from_template(template_name: String) -> Result<Self, &'static str> {
@@ -69,11 +73,16 @@ impl Game {
             board,
             active_player,
             throwing_player: active_player,
+            turn: 0,
             cards_played: 0,
             phase: phase.clone(),
             phase_count: 0,
             card_count: 0,
             robber_count: 0,
+            discard_queue: vec![],
+            gold_queue: vec![],
+            gold_owed: vec![0; parameters.n_players as usize],
+            ship_moved: false,
             action: None,
             outcome: None,
         })
@@ -81,19 +90,32 @@ impl Game {
 
     fn initialize_from_parameters(parameters: GameParameters, start: SystemTime) -> Result<Self, &'static str> {
         // initialize round
-        let round = Self::initialize_round(&parameters)?;
+        let mut round = Self::initialize_round(&parameters)?;
+
+        // initialize what no player sees
+        let hidden = HiddenState::new(&parameters, &mut round.board.rng);
 
         // initialize result
         let result: Option<Summary> = None;
 
         let log = log_entry::initialize_log(&round, start);
 
-        Ok(Self {
+        let game = Self {
             parameters,
             round,
             log,
             result,
-        })
+            hidden,
+        };
+
+        // simple NN players can only evaluate the encoding they were trained on
+        let has_simple_nn = game.parameters.v_players.iter().any(|player| matches!(player.player_type, PlayerType::SimpleNN(_)));
+
+        if has_simple_nn && !fits_network(&game) {
+            return Err("The simple NN player only plays games in the base log encoding of the default board.")
+        }
+
+        Ok(game)
     }
 
     pub fn run(&mut self) -> Result<(), &'static str> {
@@ -199,7 +221,13 @@ impl Game {
 
                             if human_present {
                                 let ui_parameters = UIBoardParameters::default();
-                                println!("Player {:?} selected {}",self.round.active_player, get_pretty_action(action, self, &ui_parameters));
+
+                                match action {
+                                    // discards are revealed once everyone has discarded
+                                    Action::DiscardCards(_) => println!("Player {:?} chose a discard.", self.round.active_player),
+                                    _ => println!("Player {:?} selected {}",self.round.active_player, get_pretty_action(action, self, &ui_parameters)),
+                                }
+
                                 sleep(Duration::from_secs(1));
                             }
                         },
@@ -223,7 +251,7 @@ impl Game {
                 return Err("Player failed to select a legal action.");
             }
 
-            if self.round.board.scores[self.round.active_player as usize] >= 10 {
+            if self.round.board.scores[self.round.active_player as usize] >= self.parameters.n_winning_vp {
                 // self.draw_board(ui_parameters.clone());
                 // println!("Player {} won!", self.round.active_player);
                 // println!("Public scores: {:?}", self.round.board.public_scores);
@@ -247,6 +275,8 @@ impl Game {
                     println!("Scores: {:?}", self.round.board.scores);
                 }
 
+                self.reveal_victory_cards();
+
                 self.result = Some(Summary::new(&self));
 
                 break
@@ -1,32 +1,54 @@
-use std::{collections::HashMap, vec};
+use std::{collections::HashMap, error::Error, sync::OnceLock, vec};
 
-use crate::backend::{io::read_parameters::{read_csv_to_option, read_deserialized_csv, read_harbors_csv, read_matrix_csv}, round::phase::Phase};
+use crate::backend::{io::{encode_log::LogEncoding, template::Template, template_registry::load_embedded}, round::{bank::{RESOURCE_NAMES, RESOURCE_SUPPLY, RESOURCE_SUPPLY_EXTENDED}, phase::Phase, pieces::BUILDING_NAMES}};
 
-use super::{city::City, edge::{create_unique_edges, edge_index_map}, harbor::Harbor, player::Player, road::Road, settlement::Settlement, shape::{get_n_node, get_node_adjacency, get_tile_nodes, TileShape}};
+use super::{city::City, dice_source::DiceMode, edge::{create_unique_edges, edge_index_map}, harbor::Harbor, player::Player, rng_service::derive_seed, road::Road, ruleset::Ruleset, settlement::Settlement, setup_payout::SetupPayout, shape::{get_layout_rings, get_n_node, get_node_adjacency, get_tile_nodes, TileShape}};
 
 use uuid::Uuid;
 
+// the default parameters are built from it without walking the template search path
+static DEFAULT_TEMPLATE: OnceLock<Template> = OnceLock::new();
+
 #[derive(Debug, Clone)]
 pub struct GameParameters {
     pub id: String,
     pub title: Option<String>,
 
+    pub ruleset: Ruleset,
+
     pub dice_seed: u64,
     pub n_dice: u32,
     pub n_faces: u32,
+    pub dice_mode: DiceMode,
+    // the event deck is reshuffled once this many cards are left
+    pub event_deck_reshuffle: u32,
+    // event die of Cities & Knights
+    pub event_seed: u64,
 
     pub robber_seed: u64,
     pub init_v_robber: Option<Vec<u32>>,
     pub robber_nos: Vec<u32>,
-
-    tile_seed: u64,
+    // players holding more resources than the hand limit discard half of them when the robber is activated
+    pub hand_limit: u32,
+    // friendly robber: players with at most this many (public) victory points cannot be robbed
+    pub friendly_robber: Option<u32>,
+    // the robber goes back to the desert once the player who moved it throws the dice again
+    pub robber_returns_to_desert: bool,
+    pub robber_on_desert: bool,
+    // pirates block coastal edges (indices of the edge map). None: no pirate in play
+    pub init_v_pirate: Option<Vec<u32>>,
+
+    pub tile_seed: u64,
     pub tile_shape: TileShape,
     pub n_tile_rings: u32,
 
     pub n_players: u32,
     pub v_players: Vec<Player>,
+    // every AI seat draws its tie breaks from its own stream derived from this seed
+    pub ai_seed: u64,
 
     pub n_resources: u32,
+    pub resource_supply: Vec<u32>,
 
     pub init_player: u32,
     pub init_phase: Phase,
@@ -64,10 +86,25 @@ pub struct GameParameters {
     pub max_trades: u32,
     pub max_cards: u32,
     pub max_builds: u32,
+    // roads, settlements and cities per player
+    pub max_pieces: Vec<u32>,
+    // ships per player (only on boards with sea tiles)
+    pub max_ships: u32,
+    // victory points for the first settlement of a player on an island other than the home islands
+    pub island_bonus: u32,
 
     pub n_setup_rounds: u32,
+    pub setup_payout: SetupPayout,
+    // setup round (counting from 0) in which a city is placed instead of a settlement, e.g. Some(1) for Cities & Knights
+    pub setup_city_round: Option<u32>,
+    // let every other player build between two turns (5-6 player rules)
+    pub special_building_phase: bool,
 
+    // road, settlement, city, development card, ship
     pub building_costs: Vec<Vec<u32>>,
+
+    // layout of the hot encoded logs (the simple network reads the base layout)
+    pub log_encoding: LogEncoding,
 }
 
 impl Default for GameParameters {
@@ -75,61 +112,57 @@ impl Default for GameParameters {
 
         let n_tile_rings = 3;
 
-        // read in tile resource assignment
-        let tile_resource_path = "data/templates/default/tile_resource.csv";
-        let v_tile_resource: Vec<Option<u32>> = read_csv_to_option(tile_resource_path).expect("An error occurred while reading tile resource file.");
-
-        // read in harbours
-        let harbor_path = "data/templates/default/harbors.csv";
-        let v_harbors_raw = read_harbors_csv(harbor_path).expect("Error while reading harbors.");
-        
-        let mut v_harbors: Vec<Harbor> = vec![];
-        for row in v_harbors_raw {
-            v_harbors.push(Harbor::new(row))
-        }
+        // tiles, number tokens, harbours and building costs of the built-in default template, read once
+        let template = DEFAULT_TEMPLATE.get_or_init(|| load_embedded("default").expect("Error while reading the default template."));
 
-        // read initial dice outcome assignments
-        let rng_path = "data/templates/default/init_tile_rng.csv";
-        let v_rng: Vec<Option<u32>> = read_csv_to_option(rng_path).expect("An error occurred while reading rng file.");
+        let v_tile_resource: Vec<Option<u32>> = template.tiles.iter().map(|tile| tile.resource).collect();
+        let v_rng: Vec<Option<u32>> = template.tiles.iter().map(|tile| tile.rng).collect();
 
+        let v_harbors: Vec<Harbor> = template.harbors.iter()
+                                        .map(|harbor| Harbor::new((harbor.first_node, harbor.second_node, harbor.harbor_type)))
+                                        .collect();
 
-        // node adjacency
-        let node_adjacency = get_node_adjacency(n_tile_rings, &TileShape::Hexagon);
+        let costs = template.costs.as_ref().expect("The default template has no building costs.");
+        let building_costs: Vec<Vec<u32>> = BUILDING_NAMES.iter()
+                                                .map(|building| {
+                                                    let resource_costs = costs.get(*building).expect("The default template does not price every building.");
+                                                    RESOURCE_NAMES.iter().map(|resource| resource_costs.get(*resource).copied().unwrap_or(0)).collect()
+                                                })
+                                                .collect();
 
-        let unique_edges = create_unique_edges(node_adjacency.clone());
-        let edge_map = edge_index_map(&unique_edges);
 
-        // tile-node adjacency
-        let tile_nodes = get_tile_nodes(n_tile_rings, &TileShape::Hexagon);
+        // node adjacency, edges and tile-node adjacency
+        let (node_adjacency, node_tiles_adjacency, tile_nodes, edge_map) = get_topology(n_tile_rings, &TileShape::Hexagon);
 
         let n_players = 4;
         let v_players: Vec<Player> = (0..n_players).map(|i| Player::new_empty(i)).collect();
 
         let n_dev_card_types = 5;
-        // order of development cards: 5 VP cards, 14 knight cards, 2 roads, 2 years of plenty, 2 monopolies
-        let init_undrawn_dev_cards = vec![5, 14, 2, 2, 2];
-
-        let n_nodes = get_n_node(n_tile_rings, &TileShape::Hexagon) as usize;
-        
-        let mut node_tiles_adjacency: Vec<Vec<usize>> = vec![vec![]; n_nodes];
-        for (i_tile, v_nodes) in tile_nodes.iter().enumerate() {
-            for i_node in v_nodes.iter() {
-                node_tiles_adjacency[*i_node as usize].push(i_tile)
-            }
-        }
-        
+        // order of development cards: 5 VP cards, 14 knight cards, 2 roads, 2 years of plenty, 2 monopolies (more for 5-6 players)
+        let init_undrawn_dev_cards = get_dev_card_deck(n_players);
+
 
         Self { 
             id: Uuid::new_v4().to_string(),
             title: None,
 
+            ruleset: Ruleset::Base,
+
             dice_seed: 42, 
             n_dice: 2, 
             n_faces: 6,
+            dice_mode: DiceMode::Dice,
+            event_deck_reshuffle: 5,
+            event_seed: 2222,
 
             robber_seed: 4444,
             init_v_robber: None,
             robber_nos: vec![7],
+            hand_limit: 7,
+            friendly_robber: None,
+            robber_returns_to_desert: false,
+            robber_on_desert: false,
+            init_v_pirate: None,
 
             tile_seed: 123, 
             tile_shape: TileShape::Hexagon,
@@ -137,8 +170,10 @@ impl Default for GameParameters {
 
             n_players, 
             v_players,
+            ai_seed: 6666,
 
             n_resources: 5,
+            resource_supply: vec![RESOURCE_SUPPLY; 5],
 
             init_player: 0,
             init_phase: Phase::SetUp,
@@ -176,10 +211,18 @@ impl Default for GameParameters {
             max_trades: 5,
             max_cards: 1,
             max_builds: 5,
+            max_pieces: vec![15, 5, 4],
+            max_ships: 15,
+            island_bonus: 2,
             
             n_setup_rounds: 2,
+            setup_payout: SetupPayout::SecondRound,
+            setup_city_round: None,
+            special_building_phase: false,
+
+            building_costs,
 
-            building_costs: vec![vec![1, 0, 1, 0, 0], vec![1, 1, 1, 0, 1], vec![0, 2, 0, 3, 0], vec![0, 1, 0, 1, 1]],
+            log_encoding: LogEncoding::Base,
         }
     } 
 }
@@ -197,67 +240,142 @@ impl GameParameters {
 
     pub fn default_from_template(self, title: Option<String>, template: String) -> Self {
 
-        let n_tile_rings = 3;
+        self.try_from_template(title, template, false).unwrap_or_else(|e| panic!("Invalid template: {}", e))
+    }
+
+    // a JSON template (data/templates/<template>.json) or a directory of CSV files, validated before the game is set up
+    pub fn try_from_template(self, title: Option<String>, template: String, settled: bool) -> Result<Self, Box<dyn Error>> {
+
+        Template::load(&template)?.to_parameters(title, settled)
+    }
 
-        // read in tile resource assignment
-        let tile_resource_path = "data/templates/".to_owned() + &template + "/tile_resource.csv";
-        let v_tile_resource: Vec<Option<u32>> = read_csv_to_option(tile_resource_path).expect("An error occurred while reading tile resource file.");
-
-        // read in harbours
-        let harbor_path = "data/templates/".to_owned() + &template + "/harbors.csv";
-        let v_harbors_raw = read_harbors_csv(&harbor_path).expect("Error while reading harbors.");
-        
-        let mut v_harbors: Vec<Harbor> = vec![];
-        for row in v_harbors_raw {
-            v_harbors.push(Harbor::new(row))
+    // rebuild the board topology for a board with n_tile_rings rings of tiles
+    pub fn with_tile_rings(self, n_tile_rings: u32) -> Self {
+
+        let (node_adjacency, node_tiles_adjacency, tile_nodes, edge_map) = get_topology(n_tile_rings, &self.tile_shape);
+
+        Self {
+            n_tile_rings,
+            node_adjacency,
+            node_tiles_adjacency,
+            tile_nodes,
+            edge_map,
+            ..self
         }
+    }
 
-        // read initial dice outcome assignments
-        let rng_path = "data/templates/".to_owned() + &template + "/init_tile_rng.csv";
-        let v_rng: Vec<Option<u32>> = read_csv_to_option(&rng_path).expect("An error occurred while reading rng file.");
+    // rebuild the board topology for tiles at arbitrary axial coordinates
+    pub fn with_layout(self, layout: Vec<(i32, i32)>) -> Self {
 
-        let budgets_path = "data/templates/".to_owned() + &template + "/init_budgets.csv";
-        let init_budgets = read_matrix_csv(&budgets_path).ok();
+        let n_tile_rings = get_layout_rings(&layout);
+        let tile_shape = TileShape::Layout(layout);
 
-        let default: GameParameters = Default::default();
-        let n_players: u32;
-        match &init_budgets {
-            Some(vec) => n_players = vec.len() as u32,
-            None => n_players = default.n_players,
+        let (node_adjacency, node_tiles_adjacency, tile_nodes, edge_map) = get_topology(n_tile_rings, &tile_shape);
+
+        Self {
+            tile_shape,
+            n_tile_rings,
+            node_adjacency,
+            node_tiles_adjacency,
+            tile_nodes,
+            edge_map,
+            ..self
+        }
+    }
+
+    // resize the player list and switch to the 5-6 player deck, bank and special building phase if necessary
+    pub fn with_n_players(self, n_players: u32) -> Self {
+
+        let mut v_players = self.v_players.clone();
+        v_players.truncate(n_players as usize);
+        for i_player in v_players.len() as u32..n_players {
+            v_players.push(Player::new_empty(i_player));
         }
 
         Self {
-            title: title, 
-            init_harbors: Some(v_harbors), 
-            v_tile_resources: v_tile_resource, 
-            init_tile_rng: v_rng, 
             n_players,
-            ..Default::default()
+            v_players,
+            init_undrawn_dev_cards: get_dev_card_deck(n_players),
+            special_building_phase: n_players > 4,
+            resource_supply: vec![if n_players > 4 { RESOURCE_SUPPLY_EXTENDED } else { RESOURCE_SUPPLY }; self.n_resources as usize],
+            ..self
+        }
+    }
+
+    // Cities & Knights: a city in the second setup round, 13 victory points to win, and progress cards instead of development cards
+    pub fn with_ruleset(self, ruleset: Ruleset) -> Self {
+        match ruleset {
+            Ruleset::Base => Self { ruleset, ..self },
+            Ruleset::CitiesAndKnights => Self {
+                ruleset,
+                n_winning_vp: 13,
+                setup_city_round: Some(1),
+                init_undrawn_dev_cards: vec![0; self.n_dev_card_types],
+                ..self
+            },
         }
+    }
 
+    // derive the seeds of all random streams (map, dice, events, steals, development cards and AI seats) from one seed
+    pub fn with_master_seed(self, master_seed: u64) -> Self {
+        Self {
+            tile_seed: derive_seed(master_seed, 0),
+            dice_seed: derive_seed(master_seed, 1),
+            event_seed: derive_seed(master_seed, 2),
+            robber_seed: derive_seed(master_seed, 3),
+            dev_card_seed: derive_seed(master_seed, 4),
+            ai_seed: derive_seed(master_seed, 5),
+            ..self
+        }
     }
 
-    
+    // seeds of the i-th game of a series (league, tournament, simulations): all streams but the map are derived from the
+    // dice seed of the series and the game index, so that neighbouring games do not share draws
+    pub fn with_game_index(self, i_game: u64) -> Self {
+        let tile_seed = self.tile_seed;
+        let master_seed = derive_seed(self.dice_seed, i_game);
+
+        Self {
+            tile_seed,
+            ..self.with_master_seed(master_seed)
+        }
+    }
+
+
     pub fn default_settled(self, title: Option<String>, template: String) -> Self {
 
-        let mut game_parameters = self.default_from_template(title, template.clone());
+        self.try_from_template(title, template, true).unwrap_or_else(|e| panic!("Invalid template: {}", e))
+    }
+}
+
 
-        
+// node adjacency, node-tile adjacency, tile-node adjacency and edge indices of a board
+pub fn get_topology(n_tile_rings: u32, tile_shape: &TileShape) -> (Vec<Vec<Option<u32>>>, Vec<Vec<usize>>, Vec<Vec<u32>>, HashMap<(u32, u32), usize>) {
 
-        let roads_path = "data/templates/".to_owned() + &template + "/init_roads.csv";
-        let result: Vec<Road> = read_deserialized_csv(&roads_path).expect("Error while reading roads.");
-        game_parameters.init_roads = Some(result);
+    let node_adjacency = get_node_adjacency(n_tile_rings, tile_shape);
 
-        let settlements_path = "data/templates/".to_owned() + &template + "/init_settlements.csv";
-        let result: Vec<Settlement> = read_deserialized_csv(&settlements_path).expect("Error while reading roads.");
-        game_parameters.init_settlements = Some(result);
+    let unique_edges = create_unique_edges(node_adjacency.clone());
+    let edge_map = edge_index_map(&unique_edges);
 
-        let cities_path = "data/templates/".to_owned() + &template + "/init_cities.csv";
-        game_parameters.init_cities = read_deserialized_csv(&cities_path).ok();
+    let tile_nodes = get_tile_nodes(n_tile_rings, tile_shape);
 
-        let budgets_path = "data/templates/".to_owned() + &template + "/init_budgets.csv";
-        game_parameters.init_budgets = read_matrix_csv(&budgets_path).ok();
+    let n_nodes = get_n_node(n_tile_rings, tile_shape) as usize;
 
-        game_parameters
+    let mut node_tiles_adjacency: Vec<Vec<usize>> = vec![vec![]; n_nodes];
+    for (i_tile, v_nodes) in tile_nodes.iter().enumerate() {
+        for i_node in v_nodes.iter() {
+            node_tiles_adjacency[*i_node as usize].push(i_tile)
+        }
+    }
+
+    (node_adjacency, node_tiles_adjacency, tile_nodes, edge_map)
+}
+
+// order of development cards: VP cards, knights, roads, years of plenty, monopolies
+pub fn get_dev_card_deck(n_players: u32) -> Vec<u32> {
+    if n_players > 4 {
+        vec![5, 20, 3, 3, 3]
+    } else {
+        vec![5, 14, 2, 2, 2]
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,55 @@
+use rand::seq::SliceRandom;
+
+use crate::backend::round::{cards::get_shuffled_dev_deck, cities_knights::{get_shuffled_progress_decks, N_COMMODITIES}};
+
+use super::{game::Game, game_parameters::GameParameters, rng_service::{RngService, Stream}, ruleset::Ruleset};
+
+// state that no player may see. It lives on the game but outside the round, and only the engine (crate::backend) can read it.
+// AI players that try out actions on a copy of the game use clone_for_lookahead, which scrambles it
+#[derive(Debug, Clone, Default)]
+pub struct HiddenState {
+    // discards that have been chosen but not yet applied: (player, discarded resources, index of the log entry of the discard)
+    pub pending_discards: Vec<(u32, Vec<u32>, usize)>,
+    // shuffled development cards. The top card is the last one
+    pub dev_deck: Vec<u32>,
+    // shuffled progress cards of the science, trade and politics decks (Cities & Knights). The top card is the last one
+    pub progress_decks: Vec<Vec<u32>>,
+}
+
+impl HiddenState {
+    pub fn new(parameters: &GameParameters, rng: &mut RngService) -> Self {
+        let dev_deck = get_shuffled_dev_deck(&parameters.init_undrawn_dev_cards, rng.rng(Stream::DevDeck));
+
+        let progress_decks = match parameters.ruleset {
+            Ruleset::CitiesAndKnights => get_shuffled_progress_decks(rng.rng(Stream::Events)),
+            Ruleset::Base => vec![vec![]; N_COMMODITIES],
+        };
+
+        HiddenState {
+            pending_discards: vec![],
+            dev_deck,
+            progress_decks,
+        }
+    }
+}
+
+
+impl Game {
+    // copy of the game for AI players that try out an action. The decks are reshuffled with the active seat's generator
+    // and the discards of the other players are dropped, so that a lookahead does not reveal the next card or a hand
+    pub fn clone_for_lookahead(&self) -> Game {
+        let mut game = self.clone();
+        let mut rng = self.get_seat_rng();
+        let active_player = self.round.active_player;
+
+        game.hidden.dev_deck.shuffle(&mut rng);
+
+        for deck in game.hidden.progress_decks.iter_mut() {
+            deck.shuffle(&mut rng);
+        }
+
+        game.hidden.pending_discards.retain(|(i_player, _, _)| *i_player == active_player);
+
+        game
+    }
+}
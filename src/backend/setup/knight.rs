@@ -0,0 +1,11 @@
+use serde::Deserialize;
+
+// knights stand on nodes and defend Catan against the barbarians (Cities & Knights)
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct Knight {
+    pub player: u32,
+    pub node: u32,
+    // basic (1), strong (2) or mighty (3)
+    pub level: u32,
+    pub active: bool,
+}
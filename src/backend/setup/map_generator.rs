@@ -0,0 +1,304 @@
+use std::collections::HashMap;
+
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+use super::{game_parameters::GameParameters, harbor::Harbor, shape::{get_n_tiles, TileShape}};
+
+
+#[derive(Debug, Clone)]
+pub struct MapConstraints {
+    // number of land tiles per resource (B, G, L, O, W)
+    pub resource_counts: Vec<u32>,
+    pub n_deserts: u32,
+    // one number token per non-desert tile
+    pub number_tokens: Vec<u32>,
+    // one harbor per entry, 0-4 = 2:1 resource harbor, 5 = 3:1 harbor
+    pub harbor_types: Vec<u32>,
+
+    pub no_adjacent_red_numbers: bool,
+    // maximum difference between the highest and the lowest mean pips per tile across resources
+    pub max_pip_spread: Option<f64>,
+
+    pub max_attempts: u32,
+}
+
+impl Default for MapConstraints {
+    fn default() -> Self {
+        Self {
+            resource_counts: vec![3, 4, 4, 3, 4],
+            n_deserts: 1,
+            number_tokens: vec![2, 3, 3, 4, 4, 5, 5, 6, 6, 8, 8, 9, 9, 10, 10, 11, 11, 12],
+            harbor_types: vec![5, 5, 5, 5, 0, 1, 2, 3, 4],
+
+            no_adjacent_red_numbers: true,
+            max_pip_spread: Some(1.0),
+
+            max_attempts: 10000,
+        }
+    }
+}
+
+impl MapConstraints {
+    // scale the standard tile, token and harbor distribution to a board with n_tile_rings rings
+    pub fn for_n_tile_rings(n_tile_rings: u32) -> Self {
+
+        let default: MapConstraints = Default::default();
+
+        if n_tile_rings == 3 {
+            return default
+        }
+
+        let n_tiles = get_n_tiles(n_tile_rings, &TileShape::Hexagon);
+        let n_deserts = (n_tiles / 19).max(1).min(n_tiles);
+        let n_land = n_tiles - n_deserts;
+
+        // hand out land tiles in the order grain, lumber, wool, brick, ore
+        let resource_order = [1, 2, 4, 0, 3];
+        let mut resource_counts = vec![0; 5];
+        for i_land in 0..n_land {
+            resource_counts[resource_order[i_land as usize % 5]] += 1;
+        }
+
+        let number_tokens = (0..n_land)
+                                .map(|i_land| default.number_tokens[i_land as usize % default.number_tokens.len()])
+                                .collect();
+
+        // the standard board has 9 harbors along 30 coastal edges
+        let n_coast_edges = 6 * (2 * n_tile_rings - 1);
+        let n_harbors = n_coast_edges * 9 / 30;
+        let harbor_types = (0..n_harbors)
+                                .map(|i_harbor| default.harbor_types[i_harbor as usize % default.harbor_types.len()])
+                                .collect();
+
+        Self {
+            resource_counts,
+            n_deserts,
+            number_tokens,
+            harbor_types,
+            ..default
+        }
+    }
+}
+
+
+impl GameParameters {
+    // replace the tiles, number tokens and harbors by a random map, reproducible from tile_seed
+    pub fn with_random_map(mut self, constraints: &MapConstraints) -> Result<Self, &'static str> {
+
+        let n_tiles = self.tile_nodes.len();
+
+        let n_land: u32 = constraints.resource_counts.iter().sum();
+        if (n_land + constraints.n_deserts) as usize != n_tiles {
+            return Err("The number of resource tiles and deserts does not match the number of tiles on the board.")
+        }
+
+        if constraints.number_tokens.len() != n_land as usize {
+            return Err("Please provide exactly one number token per resource tile.")
+        }
+
+        let tile_neighbors = get_tile_neighbors(&self.tile_nodes);
+
+        let mut rng = StdRng::seed_from_u64(self.tile_seed);
+
+        let mut v_tile_resources: Vec<Option<u32>> = vec![None; constraints.n_deserts as usize];
+        for (resource, &count) in constraints.resource_counts.iter().enumerate() {
+            for _ in 0..count {
+                v_tile_resources.push(Some(resource as u32));
+            }
+        }
+
+        let mut number_tokens = constraints.number_tokens.clone();
+
+        let mut found = false;
+        let mut v_tile_rng: Vec<Option<u32>> = vec![None; n_tiles];
+        for _ in 0..constraints.max_attempts {
+            v_tile_resources.shuffle(&mut rng);
+            number_tokens.shuffle(&mut rng);
+
+            // deserts do not receive a number token
+            let mut i_token = 0;
+            for i_tile in 0..n_tiles {
+                v_tile_rng[i_tile] = match v_tile_resources[i_tile] {
+                    Some(_) => {
+                        i_token += 1;
+                        Some(number_tokens[i_token - 1])
+                    },
+                    None => None,
+                };
+            }
+
+            if constraints.no_adjacent_red_numbers && has_adjacent_red_numbers(&v_tile_rng, &tile_neighbors) {
+                continue
+            }
+
+            match constraints.max_pip_spread {
+                Some(max_spread) => {
+                    if get_pip_spread(&v_tile_resources, &v_tile_rng, constraints.resource_counts.len()) > max_spread {
+                        continue
+                    }
+                },
+                None => (),
+            }
+
+            found = true;
+            break;
+        }
+
+        if !found {
+            return Err("Could not generate a map satisfying the constraints within the maximum number of attempts.")
+        }
+
+        // harbors are spread evenly along the coastline
+        let coast_edges = self.get_coast_edges();
+        let n_harbors = constraints.harbor_types.len();
+
+        if 2 * n_harbors > coast_edges.len() {
+            return Err("Too many harbors for the length of the coastline.")
+        }
+
+        let mut harbor_types = constraints.harbor_types.clone();
+        harbor_types.shuffle(&mut rng);
+
+        let offset = rng.gen_range(0..coast_edges.len());
+        let v_harbors: Vec<Harbor> = harbor_types.iter()
+                                        .enumerate()
+                                        .map(|(i_harbor, &harbor_type)| {
+                                            let i_edge = (offset + i_harbor * coast_edges.len() / n_harbors) % coast_edges.len();
+                                            Harbor::new((coast_edges[i_edge].0, coast_edges[i_edge].1, harbor_type))
+                                        })
+                                        .collect();
+
+        self.v_tile_resources = v_tile_resources;
+        self.init_tile_rng = v_tile_rng;
+        self.init_harbors = Some(v_harbors);
+        self.init_v_robber = None;
+
+        Ok(self)
+    }
+
+    // coastal edges (edges bordering exactly one tile) in the order in which they are met walking around the board
+    pub fn get_coast_edges(&self) -> Vec<(u32, u32)> {
+
+        let mut node_coast_edges: HashMap<u32, Vec<(u32, u32)>> = HashMap::new();
+
+        let mut edges: Vec<&(u32, u32)> = self.edge_map.keys().collect();
+        edges.sort();
+
+        for &&(a, b) in edges.iter() {
+            let n_tiles = self.node_tiles_adjacency[a as usize].iter()
+                            .filter(|i_tile| self.node_tiles_adjacency[b as usize].contains(i_tile))
+                            .count();
+
+            if n_tiles == 1 {
+                node_coast_edges.entry(a).or_default().push((a, b));
+                node_coast_edges.entry(b).or_default().push((a, b));
+            }
+        }
+
+        let first_edge = match edges.iter().find(|edge| node_coast_edges.get(&edge.0).map_or(false, |v| v.contains(edge))) {
+            Some(&&edge) => edge,
+            None => return vec![],
+        };
+
+        let mut coast_edges = vec![first_edge];
+        let mut current_node = first_edge.1;
+
+        loop {
+            let last_edge = coast_edges[coast_edges.len() - 1];
+            let next_edge = match node_coast_edges.get(&current_node) {
+                Some(v) => match v.iter().find(|&&edge| edge != last_edge) {
+                    Some(&edge) => edge,
+                    None => break,
+                },
+                None => break,
+            };
+
+            if next_edge == first_edge {
+                break
+            }
+
+            current_node = if next_edge.0 == current_node { next_edge.1 } else { next_edge.0 };
+            coast_edges.push(next_edge);
+        }
+
+        coast_edges
+    }
+}
+
+
+// tiles are neighbors if they share an edge, i.e. two nodes
+pub fn get_tile_neighbors(tile_nodes: &Vec<Vec<u32>>) -> Vec<Vec<usize>> {
+
+    let mut tile_neighbors: Vec<Vec<usize>> = vec![vec![]; tile_nodes.len()];
+
+    for i_tile in 0..tile_nodes.len() {
+        for j_tile in 0..tile_nodes.len() {
+            if i_tile == j_tile {
+                continue
+            }
+
+            let n_shared = tile_nodes[i_tile].iter().filter(|node| tile_nodes[j_tile].contains(node)).count();
+
+            if n_shared >= 2 {
+                tile_neighbors[i_tile].push(j_tile);
+            }
+        }
+    }
+
+    tile_neighbors
+}
+
+pub fn get_pips(rng: u32) -> u32 {
+    if rng < 2 || rng > 12 {
+        0
+    } else {
+        6 - (7_i32 - rng as i32).unsigned_abs()
+    }
+}
+
+fn has_adjacent_red_numbers(v_tile_rng: &Vec<Option<u32>>, tile_neighbors: &Vec<Vec<usize>>) -> bool {
+    let is_red = |rng: &Option<u32>| *rng == Some(6) || *rng == Some(8);
+
+    for (i_tile, neighbors) in tile_neighbors.iter().enumerate() {
+        if !is_red(&v_tile_rng[i_tile]) {
+            continue
+        }
+
+        if neighbors.iter().any(|&j_tile| is_red(&v_tile_rng[j_tile])) {
+            return true
+        }
+    }
+
+    false
+}
+
+fn get_pip_spread(v_tile_resources: &Vec<Option<u32>>, v_tile_rng: &Vec<Option<u32>>, n_resources: usize) -> f64 {
+
+    let mut pips = vec![0; n_resources];
+    let mut counts = vec![0; n_resources];
+
+    for (resource, rng) in v_tile_resources.iter().zip(v_tile_rng.iter()) {
+        match (resource, rng) {
+            (Some(r), Some(n)) => {
+                pips[*r as usize] += get_pips(*n);
+                counts[*r as usize] += 1;
+            },
+            _ => (),
+        }
+    }
+
+    let mean_pips: Vec<f64> = pips.iter()
+                                .zip(counts.iter())
+                                .filter(|(_, &count)| count > 0)
+                                .map(|(&p, &count)| p as f64 / count as f64)
+                                .collect();
+
+    let max = mean_pips.iter().cloned().fold(f64::MIN, f64::max);
+    let min = mean_pips.iter().cloned().fold(f64::MAX, f64::min);
+
+    if mean_pips.is_empty() {
+        0.0
+    } else {
+        max - min
+    }
+}
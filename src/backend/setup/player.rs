@@ -3,7 +3,7 @@ use std::error::Error;
 use csv::ReaderBuilder;
 use tch::{nn, Device};
 
-use crate::{ai::{classic_player, random_player, simple_nn::{self, evaluate::SimpleNN}}, backend::round::action::Action};
+use crate::{ai::{classic_player, classic_player_plus, random_player, simple_nn::{self, evaluate::SimpleNN}}, backend::{io::assets::read_asset_to_string, round::action::Action}};
 
 use super::game::Game;
 
@@ -24,7 +24,7 @@ pub enum PlayerType {
     Fixed,
     Classic(Vec<Vec<f64>>),
     ClassicPlus(Vec<Vec<f64>>),
-    SimpleNN
+    SimpleNN(SimpleNN)
 }
 
 impl Default for Player {
@@ -56,7 +56,7 @@ impl Player {
 
     pub fn new_classic(id: u32) -> Player {
         
-        let weights = read_weights("data/ai/classic/weights.csv").unwrap_or(vec![vec![0.0_f64; 13]; 5]);
+        let weights = read_weights("ai/classic/weights.csv").unwrap_or(vec![vec![0.0_f64; 13]; 5]);
         
         Self {
             id,
@@ -69,47 +69,61 @@ impl Player {
 
     pub fn new_classic_plus(id: u32) -> Player {
         
-        let weights = read_weights("data/ai/classic_plus/weights.csv").unwrap_or(vec![vec![0.0_f64; 13]; 5]);
+        let weights = read_weights("ai/classic_plus/weights.csv").unwrap_or(vec![vec![0.0_f64; 13]; 5]);
         
         Self {
             id,
             name: None,
             color_id: None,
-            player_type: PlayerType::Classic(weights),
-            player_function: Some(classic_player::play::play),
+            player_type: PlayerType::ClassicPlus(weights),
+            player_function: Some(classic_player_plus::play::play),
         }
     }
 
-    pub fn new_simple_nn(id: u32) -> Player {
+    pub fn from_type(id: u32, player_type: PlayerType) -> Player {
 
-        // let device = if tch::Cuda::is_available() {
-        //     Device::Cuda(0)
-        // } else {
-        //     Device::Cpu
-        // };
+        let player_function: Option<fn(&Game, Vec<Action>) -> Option<Action>> = match &player_type {
+            PlayerType::Human => None,
+            PlayerType::Random => Some(random_player::play::play),
+            PlayerType::Myopic => None,
+            PlayerType::Fixed => None,
+            PlayerType::Classic(_) => Some(classic_player::play::play),
+            PlayerType::ClassicPlus(_) => Some(classic_player_plus::play::play),
+            PlayerType::SimpleNN(_) => Some(simple_nn::play::play),
+        };
 
-        // // Load the model
-        // let mut vs = nn::VarStore::new(device);
-        // let model = SimpleNN::new(&vs.root(), 2368, 1);
-        // let model_path = "src/ai/simple_nn/weights_short.safetensors";
-        // vs.load(model_path).expect("PyTorch model could not be loaded");
+        Self {
+            id,
+            name: None,
+            color_id: None,
+            player_type,
+            player_function,
+        }
+    }
+
+    // fails without the trained model (see SimpleNN::load)
+    pub fn new_simple_nn(id: u32) -> Result<Player, Box<dyn Error>> {
 
+        let model = SimpleNN::load()?;
 
-        Self { 
+        Ok(Self { 
             id, 
             name: None, 
             color_id: None, 
-            player_type: PlayerType::SimpleNN, 
+            player_type: PlayerType::SimpleNN(model), 
             player_function: Some(simple_nn::play::play) 
-        }
+        })
     }
 }
 
 
-fn read_weights(file_path: &str) -> Result<Vec<Vec<f64>>, Box<dyn Error>> {
+// one row per resource, from $CATAN_ASSET_DIR, the embedded assets or data/ of the working directory
+fn read_weights(asset_path: &str) -> Result<Vec<Vec<f64>>, Box<dyn Error>> {
+    let content = read_asset_to_string(asset_path)?;
+
     let mut rdr = ReaderBuilder::new()
         .has_headers(false)
-        .from_path(file_path)?;
+        .from_reader(content.as_bytes());
 
     let mut data: Vec<Vec<f64>> = Vec::new();
 
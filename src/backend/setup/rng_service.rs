@@ -0,0 +1,107 @@
+use rand::{rngs::StdRng, SeedableRng};
+use serde::Deserialize;
+
+use super::{game::Game, game_parameters::GameParameters};
+
+// every random aspect of a game draws from its own stream, so changing one (e.g. an AI player) leaves the others untouched
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum Stream {
+    Dice,
+    Events,
+    Steals,
+    DevDeck,
+    Seat(u32),
+}
+
+impl Stream {
+    pub fn name(&self) -> String {
+        match self {
+            Stream::Dice => "dice".to_string(),
+            Stream::Events => "events".to_string(),
+            Stream::Steals => "steals".to_string(),
+            Stream::DevDeck => "dev_deck".to_string(),
+            Stream::Seat(i_seat) => format!("seat_{}", i_seat),
+        }
+    }
+}
+
+// the n-th draw of a stream is seeded with a seed derived from the stream seed and n, so that neighbouring
+// stream seeds do not share draws. The state of all streams is part of the board
+// and thereby of every log entry
+#[derive(Debug, Clone, Deserialize)]
+pub struct RngService {
+    // (stream, seed, number of draws so far)
+    streams: Vec<(Stream, u64, u64)>,
+}
+
+
+impl RngService {
+    pub fn new(parameters: &GameParameters) -> Self {
+        let mut streams = vec![
+            (Stream::Dice, parameters.dice_seed, 0),
+            (Stream::Events, parameters.event_seed, 0),
+            (Stream::Steals, parameters.robber_seed, 0),
+            (Stream::DevDeck, parameters.dev_card_seed, 0),
+        ];
+
+        for i_seat in 0..parameters.n_players {
+            streams.push((Stream::Seat(i_seat), derive_seed(parameters.ai_seed, i_seat as u64), 0));
+        }
+
+        RngService { streams }
+    }
+
+    // generator for the next draw of the stream
+    pub fn rng(&mut self, stream: Stream) -> StdRng {
+        let i_stream = self.get_index(stream);
+        let (_, seed, n_draws) = self.streams[i_stream];
+
+        self.streams[i_stream].2 += 1;
+
+        StdRng::seed_from_u64(derive_seed(seed, n_draws))
+    }
+
+    // generator for a given draw, without advancing the stream. AI players only see the game immutably
+    // and use the length of the log as draw index
+    pub fn rng_at(&self, stream: Stream, i_draw: u64) -> StdRng {
+        let (_, seed, _) = self.streams[self.get_index(stream)];
+
+        StdRng::seed_from_u64(derive_seed(seed, i_draw))
+    }
+
+    pub fn get_seed(&self, stream: Stream) -> u64 {
+        self.streams[self.get_index(stream)].1
+    }
+
+    pub fn get_n_draws(&self, stream: Stream) -> u64 {
+        self.streams[self.get_index(stream)].2
+    }
+
+    pub fn get_streams(&self) -> &Vec<(Stream, u64, u64)> {
+        &self.streams
+    }
+
+    fn get_index(&self, stream: Stream) -> usize {
+        self.streams.iter()
+            .position(|(s, _, _)| *s == stream)
+            .expect("Unknown random stream.")
+    }
+}
+
+
+impl Game {
+    // generator of the active seat for the current decision (AI players break ties with it)
+    pub fn get_seat_rng(&self) -> StdRng {
+        self.round.board.rng.rng_at(Stream::Seat(self.round.active_player), self.log.len() as u64)
+    }
+}
+
+
+// splitmix64: well separated seeds from one master seed
+pub fn derive_seed(master_seed: u64, i_stream: u64) -> u64 {
+    let mut z = master_seed.wrapping_add((i_stream + 1).wrapping_mul(0x9E3779B97F4A7C15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+
+    z ^ (z >> 31)
+}
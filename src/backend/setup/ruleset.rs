@@ -0,0 +1,9 @@
+// rules the engine plays by. The base game is unaffected by the expansion rules
+#[derive(Debug, Clone, PartialEq)]
+pub enum Ruleset {
+    Base,
+    // commodities, city improvements, knights, barbarians (event die) and progress cards.
+    // commodities count towards the hand limit and trade with the bank. Progress cards: road building, irrigation, mining,
+    // printer, resource and trade monopoly, warlord and constitution. The other progress cards are not in the decks
+    CitiesAndKnights,
+}
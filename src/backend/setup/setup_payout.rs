@@ -0,0 +1,19 @@
+// starting resources for the settlements placed during the setup
+#[derive(Debug, Clone, PartialEq)]
+pub enum SetupPayout {
+    Nothing,
+    // one resource per adjacent tile for the settlement placed in the second setup round (standard rules)
+    SecondRound,
+    // one resource per adjacent tile for every setup settlement
+    AllRounds,
+}
+
+impl SetupPayout {
+    pub fn pays_out(&self, i_setup_round: u32) -> bool {
+        match self {
+            SetupPayout::Nothing => false,
+            SetupPayout::SecondRound => i_setup_round == 1,
+            SetupPayout::AllRounds => true,
+        }
+    }
+}
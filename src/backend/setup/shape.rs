@@ -1,15 +1,23 @@
-
+use std::collections::HashMap;
 
 #[derive(Debug, Clone)]
 pub enum TileShape {
     Hexagon,
+    // axial coordinates (q, r) of every tile, in the order of the tile ids
+    Layout(Vec<(i32, i32)>),
 }
 
+// axial directions of the six neighbors of a tile. Corner k of a tile lies between its neighbors k and k+1
+const AXIAL_DIRECTIONS: [(i32, i32); 6] = [(1, 0), (1, -1), (0, -1), (-1, 0), (-1, 1), (0, 1)];
+
 pub fn get_n_node(n_tile_rings: u32, shape: &TileShape) -> u32 {
     match shape {
         TileShape::Hexagon => {
             get_n_node_hexagon(n_tile_rings)
-        }
+        },
+        TileShape::Layout(layout) => {
+            get_layout_corners(layout).len() as u32
+        },
     }
 }
 
@@ -20,7 +28,8 @@ fn get_n_node_hexagon(n_tile_rings: u32) -> u32 {
 
 pub fn get_n_node_rings(n_tile_rings: u32, shape: &TileShape) -> u32 {
     match shape {
-        TileShape::Hexagon => {
+        // a layout has no node rings of its own: it is drawn within the hexagon of n_tile_rings rings around it
+        TileShape::Hexagon | TileShape::Layout(_) => {
             get_n_node_rings_hexagon(n_tile_rings)
         }
     }
@@ -35,7 +44,10 @@ pub fn get_n_tiles(n_tile_rings: u32, shape: &TileShape) -> u32 {
     match shape {
         TileShape::Hexagon => {
             get_n_tiles_hexagon(n_tile_rings)
-        }
+        },
+        TileShape::Layout(layout) => {
+            layout.len() as u32
+        },
     }
 }
 
@@ -48,11 +60,20 @@ fn get_n_tiles_hexagon(n_tile_rings: u32) -> u32 {
 }
 
 
+// inverse of get_n_tiles: the number of tile rings of a board with n_tiles tiles (None if no full board has that many tiles)
+pub fn get_n_tile_rings(n_tiles: u32, shape: &TileShape) -> Option<u32> {
+    (1..=n_tiles.max(1)).find(|&n_tile_rings| get_n_tiles(n_tile_rings, shape) == n_tiles)
+}
+
+
 pub fn get_node_adjacency(n_tile_rings: u32, shape: &TileShape) -> Vec<Vec<Option<u32>>> {
     match shape {
         TileShape::Hexagon => {
             get_node_adjacency_hexagon(n_tile_rings)
         },
+        TileShape::Layout(layout) => {
+            get_node_adjacency_layout(layout)
+        },
     }
 }
 
@@ -164,6 +185,9 @@ pub fn get_tile_nodes(n_tile_rings: u32, shape: &TileShape) -> Vec<Vec<u32>> {
         TileShape::Hexagon => {
             get_tile_nodes_hexagon(n_tile_rings)
         },
+        TileShape::Layout(layout) => {
+            get_tile_nodes_layout(layout)
+        },
     }
 }
 
@@ -261,12 +285,125 @@ fn get_tile_nodes_hexagon(n_tile_rings: u32) -> Vec<Vec<u32>> {
 
 pub fn get_n_node_neighbors(shape: &TileShape) -> u32 {
     match shape {
-        TileShape::Hexagon => 3,
+        TileShape::Hexagon | TileShape::Layout(_) => 3,
     }
 }
 
 pub fn get_n_tile_neighbors(shape: &TileShape) -> u32 {
     match shape {
-        TileShape::Hexagon => 6,
+        TileShape::Hexagon | TileShape::Layout(_) => 6,
+    }
+}
+
+
+// a corner is identified by the three hexes which meet there (whether they belong to the board or not)
+fn get_corner_hexes(tile: (i32, i32), i_corner: usize) -> [(i32, i32); 3] {
+    let first_direction = AXIAL_DIRECTIONS[i_corner];
+    let second_direction = AXIAL_DIRECTIONS[(i_corner + 1) % 6];
+
+    let mut hexes = [
+        tile,
+        (tile.0 + first_direction.0, tile.1 + first_direction.1),
+        (tile.0 + second_direction.0, tile.1 + second_direction.1),
+    ];
+    hexes.sort();
+
+    hexes
+}
+
+// the corners of a layout, numbered in the order of their first appearance (tile by tile, corner by corner)
+pub fn get_layout_corners(layout: &Vec<(i32, i32)>) -> Vec<[(i32, i32); 3]> {
+    let mut corners: Vec<[(i32, i32); 3]> = vec![];
+
+    for &tile in layout {
+        for i_corner in 0..6 {
+            let hexes = get_corner_hexes(tile, i_corner);
+
+            if !corners.contains(&hexes) {
+                corners.push(hexes);
+            }
+        }
     }
-}
\ No newline at end of file
+
+    corners
+}
+
+fn get_tile_nodes_layout(layout: &Vec<(i32, i32)>) -> Vec<Vec<u32>> {
+    let node_ids: HashMap<[(i32, i32); 3], u32> = get_layout_corners(layout).into_iter()
+                                                    .enumerate()
+                                                    .map(|(i_node, hexes)| (hexes, i_node as u32))
+                                                    .collect();
+
+    // the six nodes of a tile go around the tile
+    layout.iter()
+        .map(|&tile| (0..6).map(|i_corner| node_ids[&get_corner_hexes(tile, i_corner)]).collect())
+        .collect()
+}
+
+fn get_node_adjacency_layout(layout: &Vec<(i32, i32)>) -> Vec<Vec<Option<u32>>> {
+    let n_nodes = get_layout_corners(layout).len();
+
+    // neighboring corners of a tile are connected by an edge
+    let mut neighbors: Vec<Vec<u32>> = vec![vec![]; n_nodes];
+    for tile_nodes in get_tile_nodes_layout(layout) {
+        for i_corner in 0..6 {
+            let node = tile_nodes[i_corner];
+            let next_node = tile_nodes[(i_corner + 1) % 6];
+
+            if !neighbors[node as usize].contains(&next_node) {
+                neighbors[node as usize].push(next_node);
+                neighbors[next_node as usize].push(node);
+            }
+        }
+    }
+
+    // as with the hexagon, every node has three neighbor slots (some of them empty on the coast)
+    neighbors.into_iter()
+        .map(|mut v_neighbors| {
+            v_neighbors.sort();
+            let mut node_neighbors: Vec<Option<u32>> = v_neighbors.into_iter().map(Some).collect();
+            node_neighbors.resize(3, None);
+            node_neighbors
+        })
+        .collect()
+}
+
+// number of hexagonal tile rings around the (rounded) centre of a layout which hold all of its tiles
+pub fn get_layout_rings(layout: &Vec<(i32, i32)>) -> u32 {
+    let n_tiles = layout.len().max(1) as f64;
+    let centre_q = (layout.iter().map(|tile| tile.0).sum::<i32>() as f64 / n_tiles).round() as i32;
+    let centre_r = (layout.iter().map(|tile| tile.1).sum::<i32>() as f64 / n_tiles).round() as i32;
+
+    let max_distance = layout.iter()
+                        .map(|&(q, r)| {
+                            let (dq, dr) = (q - centre_q, r - centre_r);
+                            ((dq.abs() + dr.abs() + (dq + dr).abs()) / 2) as u32
+                        })
+                        .max()
+                        .unwrap_or(0);
+
+    1 + max_distance
+}
+
+// every tile appears once. Cube coordinates (q, r, s) must add up to zero
+pub fn check_layout(layout: &Vec<(i32, i32, Option<i32>)>, n_tiles: usize) -> Result<Vec<(i32, i32)>, &'static str> {
+    if layout.len() != n_tiles {
+        return Err("The layout needs exactly one coordinate per tile.")
+    }
+
+    let mut axial: Vec<(i32, i32)> = vec![];
+    for &(q, r, s) in layout {
+        match s {
+            Some(s) if q + r + s != 0 => return Err("Cube coordinates of a tile must add up to zero."),
+            _ => (),
+        }
+
+        if axial.contains(&(q, r)) {
+            return Err("Two tiles of the layout have the same coordinates.")
+        }
+
+        axial.push((q, r));
+    }
+
+    Ok(axial)
+}
@@ -0,0 +1,20 @@
+use serde::Deserialize;
+
+// ships are routes on sea edges. Unlike roads, they are not stored on the nodes, since they can be moved
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq, Hash)]
+pub struct Ship {
+    pub player: u32,
+    pub nodes: (u32, u32),
+    // turn in which the ship was built (ships cannot be moved in the turn they were built)
+    pub turn: u32,
+}
+
+impl Ship {
+    pub fn is_on(&self, start_node: u32, end_node: u32) -> bool {
+        self.nodes == (start_node, end_node) || self.nodes == (end_node, start_node)
+    }
+
+    pub fn touches(&self, i_node: u32) -> bool {
+        self.nodes.0 == i_node || self.nodes.1 == i_node
+    }
+}
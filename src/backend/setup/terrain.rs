@@ -0,0 +1,103 @@
+use super::game_parameters::GameParameters;
+
+// kind of a tile. Templates encode it in the tile resource: the land resources first, then desert, sea and gold
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Terrain {
+    Land(u32),
+    Desert,
+    Sea,
+    Gold,
+}
+
+impl Terrain {
+    pub fn from_resource(resource: Option<u32>, n_resources: u32) -> Self {
+        match resource {
+            Some(r) if r < n_resources => Terrain::Land(r),
+            Some(r) if r == n_resources + 1 => Terrain::Sea,
+            Some(r) if r == n_resources + 2 => Terrain::Gold,
+            _ => Terrain::Desert,
+        }
+    }
+}
+
+
+impl GameParameters {
+
+    pub fn get_terrain(&self, i_tile: usize) -> Terrain {
+        Terrain::from_resource(self.v_tile_resources[i_tile], self.n_resources)
+    }
+
+    // ships only exist on boards with sea tiles
+    pub fn has_sea(&self) -> bool {
+        (0..self.v_tile_resources.len()).any(|i_tile| self.get_terrain(i_tile) == Terrain::Sea)
+    }
+
+    pub fn has_gold(&self) -> bool {
+        (0..self.v_tile_resources.len()).any(|i_tile| self.get_terrain(i_tile) == Terrain::Gold)
+    }
+
+    // settlements need at least one adjacent land tile
+    pub fn is_land_node(&self, i_node: u32) -> bool {
+        self.node_tiles_adjacency[i_node as usize].iter().any(|&i_tile| self.get_terrain(i_tile) != Terrain::Sea)
+    }
+
+    // roads run along land tiles
+    pub fn is_land_edge(&self, start_node: u32, end_node: u32) -> bool {
+        self.get_edge_tiles(start_node, end_node).iter().any(|&i_tile| self.get_terrain(i_tile) != Terrain::Sea)
+    }
+
+    // ships sail along sea tiles and along the rim of the board (which is surrounded by sea)
+    pub fn is_sea_edge(&self, start_node: u32, end_node: u32) -> bool {
+        let edge_tiles = self.get_edge_tiles(start_node, end_node);
+
+        edge_tiles.len() == 1 || edge_tiles.iter().any(|&i_tile| self.get_terrain(i_tile) == Terrain::Sea)
+    }
+
+    fn get_edge_tiles(&self, start_node: u32, end_node: u32) -> Vec<usize> {
+        self.node_tiles_adjacency[start_node as usize].iter()
+            .filter(|i_tile| self.node_tiles_adjacency[end_node as usize].contains(i_tile))
+            .copied()
+            .collect()
+    }
+
+    // island of every tile (None for sea): land tiles sharing a node belong to the same island
+    pub fn get_islands(&self) -> Vec<Option<usize>> {
+        let n_tiles = self.v_tile_resources.len();
+        let mut islands: Vec<Option<usize>> = vec![None; n_tiles];
+        let mut n_islands = 0;
+
+        for i_start in 0..n_tiles {
+            if islands[i_start].is_some() || self.get_terrain(i_start) == Terrain::Sea {
+                continue
+            }
+
+            // flood fill from the first tile not yet assigned
+            let mut stack = vec![i_start];
+            islands[i_start] = Some(n_islands);
+
+            while let Some(i_tile) = stack.pop() {
+                for i_node in &self.tile_nodes[i_tile] {
+                    for &i_neighbor in &self.node_tiles_adjacency[*i_node as usize] {
+                        if islands[i_neighbor].is_none() && self.get_terrain(i_neighbor) != Terrain::Sea {
+                            islands[i_neighbor] = Some(n_islands);
+                            stack.push(i_neighbor);
+                        }
+                    }
+                }
+            }
+
+            n_islands += 1;
+        }
+
+        islands
+    }
+
+    // island every node belongs to (the island of its first land tile, None for nodes at sea)
+    pub fn get_node_islands(&self) -> Vec<Option<usize>> {
+        let islands = self.get_islands();
+
+        self.node_tiles_adjacency.iter()
+            .map(|node_tiles| node_tiles.iter().find_map(|&i_tile| islands[i_tile]))
+            .collect()
+    }
+}
@@ -0,0 +1,236 @@
+use std::{error::Error, fs::{self, File}, path::PathBuf};
+
+use csv::Writer;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{league::LeagueEntrant, monte_carlo::run_games, setup::{game_parameters::GameParameters, player::Player}};
+
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SeatingDesign {
+    // every permutation of the entrants over the seats
+    AllPermutations,
+    // cyclic rotations only: every entrant sits in every seat exactly once
+    LatinSquare,
+}
+
+#[derive(Debug, Clone)]
+pub struct Tournament {
+    pub title: String,
+    pub entrants: Vec<LeagueEntrant>,
+    pub seating_design: SeatingDesign,
+    pub n_seed_sets: u32,
+    // the statistics are written to <output_dir>/<title>/
+    pub output_dir: PathBuf,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EntrantStatistics {
+    pub name: String,
+    pub n_games: u32,
+    pub n_wins: u32,
+    pub win_rate: f64,
+    pub mean_score: f64,
+    pub std_error_score: f64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PairedStatistics {
+    pub name_a: String,
+    pub name_b: String,
+    pub n_seed_sets: u32,
+    pub mean_diff_score: f64,
+    pub std_error_diff_score: f64,
+    pub mean_diff_win_rate: f64,
+    pub std_error_diff_win_rate: f64,
+}
+
+impl Tournament {
+    pub fn new(title: String, entrants: Vec<LeagueEntrant>, seating_design: SeatingDesign, n_seed_sets: u32, output_dir: PathBuf) -> Self {
+        Self {
+            title,
+            entrants,
+            seating_design,
+            n_seed_sets,
+            output_dir,
+        }
+    }
+
+    // play every seating of every seed set and write the per-entrant and paired statistics
+    pub fn run(&self, game_parameters: &GameParameters) -> Result<(Vec<EntrantStatistics>, Vec<PairedStatistics>), Box<dyn Error>> {
+
+        let n_players = game_parameters.n_players as usize;
+
+        if self.entrants.len() != n_players {
+            return Err("A tournament needs exactly one entrant per seat.".into())
+        }
+
+        let seatings = get_seatings(n_players, &self.seating_design);
+
+        // per seed set and entrant: (final score, won) of every finished game
+        let mut results: Vec<Vec<Vec<(u32, bool)>>> = vec![vec![vec![]; n_players]; self.n_seed_sets as usize];
+
+        for i_seed_set in 0..self.n_seed_sets {
+
+            let v_parameters: Vec<GameParameters> = seatings.iter()
+                .map(|seating| {
+                    let mut parameters = game_parameters.clone();
+
+                    parameters.id = Uuid::new_v4().to_string();
+                    parameters.title = Some(self.title.clone());
+
+                    parameters.v_players = seating.iter()
+                                            .enumerate()
+                                            .map(|(seat, &i_entrant)| Player::from_type(seat as u32, self.entrants[i_entrant].player_type.clone()))
+                                            .collect();
+
+                    // identical seeds for every seating of the same seed set
+                    parameters.with_game_index(i_seed_set as u64)
+                })
+                .collect();
+
+            let games = run_games(v_parameters);
+
+            // games that could not be set up keep their place, so the seatings of a seed set stay paired
+            for (game, seating) in games.iter().zip(seatings.iter()) {
+                if let Some(summary) = game.as_ref().and_then(|game| game.result.as_ref()) {
+                    for (seat, &i_entrant) in seating.iter().enumerate() {
+                        results[i_seed_set as usize][i_entrant].push((summary.final_scores[seat], summary.winner_id as usize == seat));
+                    }
+                }
+            }
+
+            println!("Tournament {}: seed set {:?} done.", self.title, i_seed_set);
+        }
+
+        let entrant_statistics = self.get_entrant_statistics(&results);
+        let paired_statistics = self.get_paired_statistics(&results);
+
+        self.write_statistics(&entrant_statistics, &paired_statistics)?;
+
+        Ok((entrant_statistics, paired_statistics))
+    }
+
+    fn get_entrant_statistics(&self, results: &Vec<Vec<Vec<(u32, bool)>>>) -> Vec<EntrantStatistics> {
+
+        (0..self.entrants.len())
+            .map(|i_entrant| {
+                let games: Vec<(u32, bool)> = results.iter()
+                                                .flat_map(|seed_set| seed_set[i_entrant].clone())
+                                                .collect();
+
+                let scores: Vec<f64> = games.iter().map(|&(score, _)| score as f64).collect();
+                let n_wins = games.iter().filter(|&&(_, won)| won).count() as u32;
+                let (mean_score, std_error_score) = get_mean_std_error(&scores);
+
+                EntrantStatistics {
+                    name: self.entrants[i_entrant].name.clone(),
+                    n_games: games.len() as u32,
+                    n_wins,
+                    win_rate: if games.is_empty() { 0.0 } else { n_wins as f64 / games.len() as f64 },
+                    mean_score,
+                    std_error_score,
+                }
+            })
+            .collect()
+    }
+
+    // differences are taken within each seed set, so the dice luck cancels out between the entrants
+    fn get_paired_statistics(&self, results: &Vec<Vec<Vec<(u32, bool)>>>) -> Vec<PairedStatistics> {
+
+        let mut paired_statistics: Vec<PairedStatistics> = vec![];
+
+        for (i_a, i_b) in (0..self.entrants.len()).tuple_combinations() {
+
+            let mut diff_scores: Vec<f64> = vec![];
+            let mut diff_win_rates: Vec<f64> = vec![];
+
+            for seed_set in results {
+                if seed_set[i_a].is_empty() || seed_set[i_b].is_empty() {
+                    continue
+                }
+
+                let (score_a, win_rate_a) = get_seed_set_means(&seed_set[i_a]);
+                let (score_b, win_rate_b) = get_seed_set_means(&seed_set[i_b]);
+
+                diff_scores.push(score_a - score_b);
+                diff_win_rates.push(win_rate_a - win_rate_b);
+            }
+
+            let (mean_diff_score, std_error_diff_score) = get_mean_std_error(&diff_scores);
+            let (mean_diff_win_rate, std_error_diff_win_rate) = get_mean_std_error(&diff_win_rates);
+
+            paired_statistics.push(PairedStatistics {
+                name_a: self.entrants[i_a].name.clone(),
+                name_b: self.entrants[i_b].name.clone(),
+                n_seed_sets: diff_scores.len() as u32,
+                mean_diff_score,
+                std_error_diff_score,
+                mean_diff_win_rate,
+                std_error_diff_win_rate,
+            });
+        }
+
+        paired_statistics
+    }
+
+    fn write_statistics(&self, entrant_statistics: &Vec<EntrantStatistics>, paired_statistics: &Vec<PairedStatistics>) -> Result<(), Box<dyn Error>> {
+
+        let dir_path = self.output_dir.join(&self.title);
+        fs::create_dir_all(&dir_path)?;
+
+        let mut wtr = Writer::from_writer(File::create(dir_path.join("entrants.csv"))?);
+        for statistics in entrant_statistics {
+            wtr.serialize(statistics)?;
+        }
+        wtr.flush()?;
+
+        let mut wtr = Writer::from_writer(File::create(dir_path.join("paired.csv"))?);
+        for statistics in paired_statistics {
+            wtr.serialize(statistics)?;
+        }
+        wtr.flush()?;
+
+        Ok(())
+    }
+}
+
+
+// seatings as entrant indices per seat
+pub fn get_seatings(n_players: usize, seating_design: &SeatingDesign) -> Vec<Vec<usize>> {
+    match seating_design {
+        SeatingDesign::AllPermutations => (0..n_players).permutations(n_players).collect(),
+        SeatingDesign::LatinSquare => (0..n_players)
+                                        .map(|i_rotation| (0..n_players).map(|seat| (seat + i_rotation) % n_players).collect())
+                                        .collect(),
+    }
+}
+
+fn get_seed_set_means(games: &Vec<(u32, bool)>) -> (f64, f64) {
+    let n_games = games.len() as f64;
+    let mean_score = games.iter().map(|&(score, _)| score as f64).sum::<f64>() / n_games;
+    let win_rate = games.iter().filter(|&&(_, won)| won).count() as f64 / n_games;
+
+    (mean_score, win_rate)
+}
+
+// sample mean and standard error of the mean
+pub fn get_mean_std_error(values: &Vec<f64>) -> (f64, f64) {
+    let n = values.len() as f64;
+
+    if values.is_empty() {
+        return (0.0, 0.0)
+    }
+
+    let mean = values.iter().sum::<f64>() / n;
+
+    if values.len() < 2 {
+        return (mean, 0.0)
+    }
+
+    let variance = values.iter().map(|value| (value - mean).powi(2)).sum::<f64>() / (n - 1.0);
+
+    (mean, (variance / n).sqrt())
+}
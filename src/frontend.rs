@@ -11,4 +11,8 @@ pub mod edges;
 pub mod buildings;
 pub mod harbors;
 pub mod roads;
-pub mod actions;
\ No newline at end of file
+pub mod ships;
+pub mod actions;
+pub mod heatmap;
+pub mod blockers;
+pub mod knights;
@@ -1,4 +1,4 @@
-use crate::backend::{round::{action::Action, cards::CardType}, setup::{game::Game, shape::get_n_node_rings}};
+use crate::backend::{round::{action::Action, cards::CardType, cities_knights::{get_commodity_trade_rate, ProgressCard}}, setup::{edge::get_edge_nodes, game::Game, shape::get_n_node_rings}};
 
 use super::{board_parameters::UIBoardParameters, coords::add_conc_coords_nodes};
 
@@ -17,13 +17,35 @@ pub fn get_pretty_action(action: &Action, game: &Game, ui_parameters: &UIBoardPa
         Action::Robber(i_robber, i_tile, i_victim) => {
             format!("Move robber {:?} to tile {:?} and steal from player {:?},", i_robber, i_tile, i_victim)
         },
+        Action::Pirate(i_pirate, i_edge, i_victim) => {
+            match get_edge_nodes(&game.parameters.edge_map, *i_edge as usize) {
+                Some((start_node, end_node)) => format!("Move pirate {:?} to the coast between {:?} and {:?} and steal from player {:?},", i_pirate, nodes[start_node as usize].coords_conc.unwrap(), nodes[end_node as usize].coords_conc.unwrap(), i_victim),
+                None => format!("Move pirate {:?} to edge {:?} and steal from player {:?},", i_pirate, i_edge, i_victim),
+            }
+        },
         Action::DiscardCards(v_discard) => {
             let names = &ui_parameters.v_resource_names;
-            format!("Discard: ({}: {:?}, {}: {:?}, {}: {:?}, {}: {:?}, {}: {:?})", names[0], v_discard[0], names[1], v_discard[1],names[2], v_discard[2],names[3], v_discard[3],names[4], v_discard[4])
+            let mut discard = format!("Discard: ({}: {:?}, {}: {:?}, {}: {:?}, {}: {:?}, {}: {:?}", names[0], v_discard[0], names[1], v_discard[1],names[2], v_discard[2],names[3], v_discard[3],names[4], v_discard[4]);
+
+            // commodities (Cities & Knights)
+            if v_discard.len() > 5 {
+                discard += &format!(", paper: {:?}, cloth: {:?}, coin: {:?}", v_discard[5], v_discard[6], v_discard[7]);
+            }
+
+            discard + ")"
         },
         Action::NoDiscard => {
             "Do not discard any resources.".to_string()
         }
+        Action::ChooseGold(v_chosen) => {
+            let names = &ui_parameters.v_resource_names;
+            let chosen: Vec<String> = v_chosen.iter()
+                                        .enumerate()
+                                        .filter(|(_, &n)| n > 0)
+                                        .map(|(i_resource, n)| format!("{} {}", n, names[i_resource]))
+                                        .collect();
+            format!("Take from the gold field: {},", chosen.join(", "))
+        },
         Action::NoCardPlay => {
             "Do not play a development card,".to_string()
         },
@@ -89,6 +111,12 @@ pub fn get_pretty_action(action: &Action, game: &Game, ui_parameters: &UIBoardPa
             // let nodes = &game.round.board.nodes;
             format!("Build road from {:?} to {:?},", nodes[*start_node as usize].coords_conc.unwrap(), nodes[*end_node as usize].coords_conc.unwrap())
         },
+        Action::BuildShip(start_node, end_node) => {
+            format!("Build ship from {:?} to {:?},", nodes[*start_node as usize].coords_conc.unwrap(), nodes[*end_node as usize].coords_conc.unwrap())
+        },
+        Action::MoveShip(old_start, old_end, new_start, new_end) => {
+            format!("Move ship from {:?}-{:?} to {:?}-{:?},", nodes[*old_start as usize].coords_conc.unwrap(), nodes[*old_end as usize].coords_conc.unwrap(), nodes[*new_start as usize].coords_conc.unwrap(), nodes[*new_end as usize].coords_conc.unwrap())
+        },
         Action::BuildSettlement(settle_node) => {
             // let nodes = &game.round.board.nodes;
             format!("Build settlement on {:?},", nodes[*settle_node as usize].coords_conc.unwrap())
@@ -97,6 +125,44 @@ pub fn get_pretty_action(action: &Action, game: &Game, ui_parameters: &UIBoardPa
             // let nodes = &game.round.board.nodes;
             format!("Build city on {:?},", nodes[*city_node as usize].coords_conc.unwrap())
         },
+        Action::BuildKnight(knight_node) => {
+            format!("Build knight on {:?},", nodes[*knight_node as usize].coords_conc.unwrap())
+        },
+        Action::ActivateKnight(knight_node) => {
+            format!("Activate knight on {:?},", nodes[*knight_node as usize].coords_conc.unwrap())
+        },
+        Action::PromoteKnight(knight_node) => {
+            format!("Promote knight on {:?},", nodes[*knight_node as usize].coords_conc.unwrap())
+        },
+        Action::BuildImprovement(i_track) => {
+            let tracks = ["science", "trade", "politics"];
+            format!("Improve {} in your cities,", tracks[*i_track as usize])
+        },
+        Action::PlayProgressCard(card) => {
+            let names = &ui_parameters.v_resource_names;
+            let commodities = ["paper", "cloth", "coin"];
+
+            match card {
+                ProgressCard::RoadBuilding(first_start, first_end, second_start, second_end) => {
+                    format!("Play 'Road Building' and build roads from {:?} to {:?} and from {:?} to {:?},", nodes[*first_start as usize].coords_conc.unwrap(), nodes[*first_end as usize].coords_conc.unwrap(), nodes[*second_start as usize].coords_conc.unwrap(), nodes[*second_end as usize].coords_conc.unwrap())
+                },
+                ProgressCard::Irrigation => "Play 'Irrigation' and take grain for your fields,".to_string(),
+                ProgressCard::Mining => "Play 'Mining' and take ore for your mountains,".to_string(),
+                ProgressCard::ResourceMonopoly(i_resource) => {
+                    format!("Play 'Resource Monopoly' and take up to 2 {} from every player,", names[*i_resource as usize])
+                },
+                ProgressCard::TradeMonopoly(i_commodity) => {
+                    format!("Play 'Trade Monopoly' and take 1 {} from every player,", commodities[*i_commodity as usize])
+                },
+                ProgressCard::Warlord => "Play 'Warlord' and activate all your knights,".to_string(),
+            }
+        },
+        Action::CommodityTrade(i_commodity, r_demanded) => {
+            let names = &ui_parameters.v_resource_names;
+            let commodities = ["paper", "cloth", "coin"];
+            let rate = get_commodity_trade_rate(game, game.round.active_player);
+            format!("Trade commodities: {} {} for 1 {},", rate, commodities[*i_commodity as usize], names[*r_demanded as usize])
+        },
         Action::BuyDevCard => {
             "Buy a development card".to_string()
         },
@@ -0,0 +1,55 @@
+use plotters::{chart::ChartContext, coord::{cartesian::Cartesian2d, types::RangedCoordf64}, element::{Circle, TriangleMarker}, style::ShapeStyle};
+use plotters_svg::SVGBackend;
+
+use crate::backend::setup::{edge::get_edge_nodes, game::Game, tile::Tile};
+
+use super::board_parameters::UIBoardParameters;
+
+// robbers sit on the centre of their tile
+pub fn draw_robbers(
+    game: &Game,
+    chart: &mut ChartContext<'_, SVGBackend<'_>, Cartesian2d<RangedCoordf64, RangedCoordf64>>, 
+    ui_parameters: &UIBoardParameters, 
+    tiles: &Vec<Tile>,
+) {
+    let fig_scale = ui_parameters.size;
+    let robber_scale = ui_parameters.robber_scale;
+    let robber_color = ui_parameters.robber_color;
+
+    for i_tile in &game.round.board.v_robbers {
+        let coords = match tiles[*i_tile as usize].coords_cart {
+            Some(c) => c,
+            None => continue,
+        };
+
+        chart.draw_series(std::iter::once(
+            Circle::new(coords, (fig_scale * robber_scale as f64) as u32, ShapeStyle::from(&robber_color).filled())
+        )).expect("Error drawing the robber");
+    }
+}
+
+// pirates sit on the middle of their coastal edge
+pub fn draw_pirates(
+    game: &Game,
+    chart: &mut ChartContext<'_, SVGBackend<'_>, Cartesian2d<RangedCoordf64, RangedCoordf64>>, 
+    ui_parameters: &UIBoardParameters, 
+    points: &Vec<(f64, f64)>,
+) {
+    let fig_scale = ui_parameters.size;
+    let pirate_scale = ui_parameters.pirate_scale;
+    let pirate_color = ui_parameters.pirate_color;
+
+    for i_edge in &game.round.board.v_pirates {
+        let (first_node_id, second_node_id) = match get_edge_nodes(&game.parameters.edge_map, *i_edge as usize) {
+            Some(edge) => edge,
+            None => continue,
+        };
+
+        let x_coord = (points[first_node_id as usize].0 + points[second_node_id as usize].0) / 2.0;
+        let y_coord = (points[first_node_id as usize].1 + points[second_node_id as usize].1) / 2.0;
+
+        chart.draw_series(std::iter::once(
+            TriangleMarker::new((x_coord, y_coord), (fig_scale * pirate_scale as f64) as u32, ShapeStyle::from(&pirate_color).filled())
+        )).expect("Error drawing the pirate");
+    }
+}
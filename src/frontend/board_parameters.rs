@@ -1,7 +1,13 @@
-use plotters::style::{full_palette::{BLACK, BROWN}, Color, RGBAColor, RGBColor};
+use std::sync::OnceLock;
 
-use super::io::read_parameters::{read_colors_csv, read_csv_to_vector};
+use plotters::style::{full_palette::{BLACK, BROWN}, register_font, Color, FontStyle, RGBAColor, RGBColor};
 
+use crate::backend::io::assets::{read_asset, read_asset_to_string};
+
+use super::io::read_parameters::{read_colors_str, read_str_to_vector};
+
+// the font of caption_font, as an asset path (see backend::io::assets)
+pub const CAPTION_FONT_PATH: &str = "fonts/DelaGothicOne-Regular.ttf";
 
 
 #[derive(Debug, Clone)]
@@ -36,6 +42,12 @@ pub struct UIBoardParameters {
     pub harbor_scale: u32,
     pub harbor_color: RGBColor,
 
+    pub robber_scale: u32,
+    pub robber_color: RGBColor,
+
+    pub pirate_scale: u32,
+    pub pirate_color: RGBColor,
+
     pub edge_color: RGBColor,
 
     pub node_label_font: &'static str,
@@ -53,6 +65,10 @@ pub struct UIBoardParameters {
 
     pub road_width: u32,
 
+    pub ship_width: u32,
+    pub ship_scale: u32,
+    pub knight_scale: u32,
+
     pub settlement_scale: u32,
     pub settlement_width: u32,
 
@@ -72,17 +88,13 @@ pub struct UIBoardParameters {
 impl Default for UIBoardParameters {
     fn default() -> Self {
 
-        let color_path = "data/parameters/tile_colors.csv";
-        let v_resource_colors = read_colors_csv(color_path).expect("Error while reading colours.");
+        let v_resource_colors = read_colors_str(&read_asset_to_string("parameters/tile_colors.csv").expect("Error while reading colours.")).expect("Error while reading colours.");
 
-        let player_color_path = "data/parameters/player_colors.csv";
-        let v_player_colors = read_colors_csv(&player_color_path).expect("Error while reading player colors.");
+        let v_player_colors = read_colors_str(&read_asset_to_string("parameters/player_colors.csv").expect("Error while reading player colors.")).expect("Error while reading player colors.");
 
-        let resource_path = "data/parameters/resources.csv";
-        let v_resource_names: Vec<String> = read_csv_to_vector(resource_path).expect("An error occurred while reading resource file.");
+        let v_resource_names: Vec<String> = read_str_to_vector(&read_asset_to_string("parameters/resources.csv").expect("An error occurred while reading resource file.")).expect("An error occurred while reading resource file.");
 
-        let harbor_sym_path = "data/parameters/harbor_symbols.csv";
-        let v_harbor_symbols: Vec<String> = read_csv_to_vector(harbor_sym_path).expect("Error while reading harbor symbols.");
+        let v_harbor_symbols: Vec<String> = read_str_to_vector(&read_asset_to_string("parameters/harbor_symbols.csv").expect("Error while reading harbor symbols.")).expect("Error while reading harbor symbols.");
 
         Self {
             size: 1.0,
@@ -115,6 +127,12 @@ impl Default for UIBoardParameters {
             harbor_scale: 5,
             harbor_color: BLACK,
 
+            robber_scale: 15,
+            robber_color: RGBColor(64, 64, 64),
+
+            pirate_scale: 20,
+            pirate_color: RGBColor(25, 50, 100),
+
             edge_color: BLACK,
 
             node_label_font: "sans-serif",
@@ -132,6 +150,10 @@ impl Default for UIBoardParameters {
 
             road_width: 15,
 
+            ship_width: 6,
+            ship_scale: 10,
+            knight_scale: 4,
+
             settlement_scale: 20,
             settlement_width: 5,
 
@@ -150,3 +172,19 @@ impl Default for UIBoardParameters {
 
     }
 }
+
+// text is laid out with the embedded font, whatever fonts are installed. Only the caption font ships with the game,
+// so it also measures the other families. The SVG files keep the family names for the viewer to render
+static REGISTERED_FONTS: OnceLock<()> = OnceLock::new();
+
+impl UIBoardParameters {
+    pub fn register_fonts(&self) {
+        REGISTERED_FONTS.get_or_init(|| {
+            let font_data: &'static [u8] = Box::leak(read_asset(CAPTION_FONT_PATH).expect("Error while reading the caption font.").into_boxed_slice());
+
+            for family in [self.caption_font, self.node_label_font, self.tile_label_font, self.harbor_label_font] {
+                register_font(family, FontStyle::Normal, font_data).map_err(|_| ()).expect("Invalid caption font.");
+            }
+        });
+    }
+}
@@ -1,6 +1,6 @@
 use std::f64::consts::PI;
 
-use crate::backend::setup::{node::Node, shape::TileShape, tile::Tile};
+use crate::backend::setup::{node::Node, shape::{get_layout_corners, get_layout_rings, get_tile_nodes, TileShape}, tile::Tile};
 
 
 pub fn add_conc_coords_nodes(mut nodes: Vec<Node>, n_node_rings: u32, tile_shape: &TileShape) -> Vec<Node> {
@@ -55,7 +55,10 @@ fn get_node_conc_coords(n_node_rings: u32, tile_shape: &TileShape) -> Vec<(u32,
     match tile_shape {
         TileShape::Hexagon => {
             get_node_conc_coords_hexagon(n_node_rings)
-        }
+        },
+        TileShape::Layout(_) => {
+            get_node_conc_coords_layout(tile_shape)
+        },
     }
 }
 
@@ -81,7 +84,10 @@ fn get_node_cart_coords(n_node_rings: u32, tile_shape: &TileShape) -> Vec<(f64,
     match tile_shape {
         TileShape::Hexagon => {
             get_node_cart_coords_hexagon(n_node_rings)
-        }
+        },
+        TileShape::Layout(layout) => {
+            get_node_cart_coords_layout(layout)
+        },
     }
 }
 
@@ -105,7 +111,10 @@ fn get_tile_cart_coords(n_tile_rings: u32, tile_shape: &TileShape) -> Vec<(f64,
     match tile_shape {
         TileShape::Hexagon => {
             get_tile_cart_coords_hexagon(n_tile_rings)
-        }
+        },
+        TileShape::Layout(layout) => {
+            layout.iter().map(|&tile| get_hex_cart_coords(tile, layout)).collect()
+        },
     }
 }
 
@@ -126,4 +135,46 @@ fn get_tile_cart_coords_hexagon(n_tile_rings: u32) -> Vec<(f64, f64)> {
     }
 
     tiles_cart
-}
\ No newline at end of file
+}
+
+
+// layouts have no node rings: a node is labelled by its first tile and its corner on that tile
+fn get_node_conc_coords_layout(tile_shape: &TileShape) -> Vec<(u32, u32)> {
+    let tile_nodes = get_tile_nodes(0, tile_shape);
+    let n_nodes = tile_nodes.iter().flatten().max().map_or(0, |&i_node| i_node as usize + 1);
+
+    let mut nodes_conc: Vec<(u32, u32)> = vec![(0, 0); n_nodes];
+
+    for (i_tile, v_nodes) in tile_nodes.iter().enumerate().rev() {
+        for (i_corner, &i_node) in v_nodes.iter().enumerate() {
+            nodes_conc[i_node as usize] = (i_tile as u32, i_corner as u32);
+        }
+    }
+
+    nodes_conc
+}
+
+// a corner lies at the centre of the three hexes that meet there
+fn get_node_cart_coords_layout(layout: &Vec<(i32, i32)>) -> Vec<(f64, f64)> {
+    get_layout_corners(layout).iter()
+        .map(|hexes| {
+            let coords: Vec<(f64, f64)> = hexes.iter().map(|&hex| get_hex_cart_coords(hex, layout)).collect();
+            (coords.iter().map(|c| c.0).sum::<f64>() / 3.0, coords.iter().map(|c| c.1).sum::<f64>() / 3.0)
+        })
+        .collect()
+}
+
+// pointy hexes, centred on the layout. As on the hexagonal board, neighboring tiles are 1 / n_tile_rings apart
+fn get_hex_cart_coords(hex: (i32, i32), layout: &Vec<(i32, i32)>) -> (f64, f64) {
+    let axial_to_cart = |(q, r): (i32, i32)| ((q as f64 + r as f64 / 2.0), -0.75_f64.sqrt() * r as f64);
+
+    let n_tiles = layout.len().max(1) as f64;
+    let centre = layout.iter()
+                    .map(|&tile| axial_to_cart(tile))
+                    .fold((0.0, 0.0), |acc, c| (acc.0 + c.0 / n_tiles, acc.1 + c.1 / n_tiles));
+
+    let scale = 1.0 / get_layout_rings(layout) as f64;
+    let coords = axial_to_cart(hex);
+
+    ((coords.0 - centre.0) * scale, (coords.1 - centre.1) * scale)
+}
@@ -3,9 +3,9 @@ use std::{fs::{self, File}, io::Write};
 use plotters::{backend::SVGBackend, chart::ChartBuilder, drawing::IntoDrawingArea, style::IntoFont};
 use regex::Regex;
 
-use crate::backend::setup::{board, game::Game, shape::get_n_node_rings};
+use crate::backend::{io::assets::get_drawing_path, setup::{board, game::Game, shape::get_n_node_rings}};
 
-use super::{board_parameters::UIBoardParameters, buildings, circles, coords::{add_cart_coords_nodes, add_cart_coords_tiles, add_conc_coords_nodes}, edges, harbors, nodes, roads, tiles};
+use super::{blockers, board_parameters::UIBoardParameters, buildings, circles, coords::{add_cart_coords_nodes, add_cart_coords_tiles, add_conc_coords_nodes}, edges, harbors, knights, nodes, roads, ships, tiles};
 
 
 
@@ -85,7 +85,10 @@ impl Game {
         }
 
         // Create a drawing area
-        let root = SVGBackend::new("data/assets/raw_board.svg", ((fig_scale * x_dim as f64) as u32, (fig_scale * y_dim as f64) as u32)).into_drawing_area();
+        ui_parameters.register_fonts();
+
+        let svg_path = get_drawing_path("raw_board.svg");
+        let root = SVGBackend::new(&svg_path, ((fig_scale * x_dim as f64) as u32, (fig_scale * y_dim as f64) as u32)).into_drawing_area();
         root.fill(&ui_parameters.bg_color).expect("Error filling the drawing area");
         // 179, 179, 179
 
@@ -118,6 +121,9 @@ impl Game {
         // Draw roads
         roads::draw(&mut chart, &ui_parameters, &points, board);
 
+        // Draw ships
+        ships::draw(&mut chart, &ui_parameters, &points, board);
+
         // Draw settlements and cities
         buildings::draw(&mut chart, &ui_parameters, &nodes);
 
@@ -127,6 +133,13 @@ impl Game {
         // Add harbour labels
         harbors::draw_labels(&self, &mut chart, &ui_parameters, &points, n_tile_rings);
 
+        // draw knights
+        knights::draw(&mut chart, &ui_parameters, &points, board);
+
+        // draw robbers and pirates
+        blockers::draw_robbers(&self, &mut chart, &ui_parameters, &tiles);
+        blockers::draw_pirates(&self, &mut chart, &ui_parameters, &points);
+
         // Draw nodes
         // nodes::draw(&mut chart, &ui_parameters, &points);
 
@@ -140,11 +153,11 @@ impl Game {
 }
 
 fn add_tiles_nodes_svg(board_name: String) -> Result<(), Box<dyn std::error::Error>> {
-    let tiles = fs::read_to_string("data/assets/tiles.svg")?;
+    let tiles = fs::read_to_string(&get_drawing_path("tiles.svg"))?;
 
-    let board = fs::read_to_string("data/assets/raw_board.svg")?;
+    let board = fs::read_to_string(&get_drawing_path("raw_board.svg"))?;
 
-    let nodes = fs::read_to_string("data/assets/nodes.svg")?;
+    let nodes = fs::read_to_string(&get_drawing_path("nodes.svg"))?;
 
     // Define the regex pattern to match polygon elements
     let re_polygon = Regex::new(r"(<polygon[^>]*>)").unwrap();
@@ -0,0 +1,81 @@
+use plotters::{chart::ChartBuilder, drawing::IntoDrawingArea, element::{Circle, IntoDynElement, Text}, style::{IntoFont, RGBColor, ShapeStyle}};
+use plotters_svg::SVGBackend;
+
+use crate::backend::{analysis::BoardAnalysis, setup::{game::Game, shape::get_n_node_rings}};
+
+use super::{board_parameters::UIBoardParameters, coords::{add_cart_coords_nodes, add_cart_coords_tiles, add_conc_coords_nodes}, edges, tiles};
+
+
+impl Game {
+
+    // draw the board with every node coloured by its expected production
+    pub fn draw_heatmap(&self, ui_parameters: UIBoardParameters, analysis: &BoardAnalysis, file_path: String) -> Result<(), Box<dyn std::error::Error>> {
+
+        let fig_scale = ui_parameters.size;
+        let x_dim = ui_parameters.x_dim;
+        let y_dim = ui_parameters.y_dim;
+
+        let x_lims = ui_parameters.x_lims;
+        let y_lims = ui_parameters.y_lims;
+
+        let n_tile_rings = self.parameters.n_tile_rings;
+
+        let board = &self.round.board;
+
+        let n_tiles = board.tiles.len();
+        let n_nodes = board.nodes.len();
+
+        let tile_shape = &self.parameters.tile_shape;
+        let n_node_rings = get_n_node_rings(n_tile_rings, tile_shape);
+
+        let mut nodes = board.nodes.clone();
+        nodes = add_conc_coords_nodes(nodes, n_node_rings, tile_shape);
+        nodes = add_cart_coords_nodes(nodes, n_node_rings, tile_shape);
+
+        let mut v_tiles = board.tiles.clone();
+        v_tiles = add_cart_coords_tiles(v_tiles, n_tile_rings, tile_shape);
+
+        let points: Vec<(f64, f64)> = nodes.iter().filter_map(|node| node.coords_cart).collect();
+        let points_tiles: Vec<(f64, f64)> = v_tiles.iter().filter_map(|tile| tile.coords_cart).collect();
+
+        ui_parameters.register_fonts();
+
+        let root = SVGBackend::new(&file_path, ((fig_scale * x_dim as f64) as u32, (fig_scale * y_dim as f64) as u32)).into_drawing_area();
+        root.fill(&ui_parameters.bg_color)?;
+
+        let mut chart = ChartBuilder::on(&root)
+            .caption(ui_parameters.caption, (ui_parameters.caption_font, (fig_scale * ui_parameters.caption_fontsize as f64 * (n_tile_rings as f64 / 3.0)) as u32).into_font().color(&ui_parameters.caption_color))
+            .margin(ui_parameters.margin)
+            .x_label_area_size(ui_parameters.x_label_size)
+            .y_label_area_size(ui_parameters.y_label_size)
+            .build_cartesian_2d(x_lims.0..x_lims.1, y_lims.0..y_lims.1)?;
+
+        tiles::draw(self, &mut chart, &ui_parameters, &v_tiles, &nodes);
+
+        edges::draw(self, &mut chart, &ui_parameters, &points, n_nodes);
+
+        tiles::draw_labels(&mut chart, &ui_parameters, &points_tiles, &v_tiles, n_tiles, n_tile_rings);
+
+        // white for unproductive nodes, deep red for the best spot on the board
+        let max_production = analysis.node_values.iter().map(|value| value.production).fold(0.0, f64::max);
+
+        for (node_value, point) in analysis.node_values.iter().zip(points.iter()) {
+            let intensity = if max_production > 0.0 { node_value.production / max_production } else { 0.0 };
+            let color = RGBColor(255, (255.0 * (1.0 - intensity)) as u8, (255.0 * (1.0 - intensity)) as u8);
+
+            chart.draw_series(std::iter::once(
+                Circle::new(*point, (fig_scale * 2.0 * ui_parameters.node_scale as f64) as u32, ShapeStyle::from(&color).filled())
+            ))?;
+
+            let label = format!("{:.0}", 36.0 * node_value.production);
+            chart.draw_series(std::iter::once(
+                Text::new(label, (point.0 - 0.01, point.1 + 0.01), (ui_parameters.node_label_font, (fig_scale * ui_parameters.node_label_fontsize as f64 / (n_tile_rings as f64 / 3.0)) as u32).into_font())
+                    .into_dyn()
+            ))?;
+        }
+
+        root.present()?;
+
+        Ok(())
+    }
+}
@@ -1,4 +1,4 @@
-use std::{fs::File, path::Path, str::FromStr};
+use std::{path::Path, str::FromStr};
 
 use csv::ReaderBuilder;
 use plotters::style::RGBColor;
@@ -14,11 +14,15 @@ struct RGBRow {
 }
 
 pub fn read_colors_csv(file_path: &str) -> Result<Vec<RGBColor>, Box<dyn Error>> {
-    let file = File::open(file_path)?;
+    read_colors_str(&std::fs::read_to_string(file_path)?)
+}
+
+// as read_colors_csv, for CSV content that is already in memory (e.g. an embedded asset)
+pub fn read_colors_str(content: &str) -> Result<Vec<RGBColor>, Box<dyn Error>> {
     let mut rdr = ReaderBuilder::new()
                             .delimiter(b',')
                             .has_headers(true)
-                            .from_reader(file);
+                            .from_reader(content.as_bytes());
 
     let mut rgb_values = Vec::new();
     for result in rdr.deserialize::<RGBRow>() {
@@ -32,8 +36,14 @@ pub fn read_csv_to_vector<T: 'static, P: AsRef<Path>>(path: P) -> Result<Vec<T>,
 where 
     T: Clone + FromStr,  < T as FromStr>::Err: std::error::Error ,
 {
-    let file = File::open(path)?;
-    let mut rdr = ReaderBuilder::new().has_headers(true).from_reader(file);
+    read_str_to_vector(&std::fs::read_to_string(path)?)
+}
+
+pub fn read_str_to_vector<T: 'static>(content: &str) -> Result<Vec<T>, Box<dyn Error>> 
+where 
+    T: Clone + FromStr,  < T as FromStr>::Err: std::error::Error ,
+{
+    let mut rdr = ReaderBuilder::new().has_headers(true).from_reader(content.as_bytes());
 
     let mut result = Vec::new();
     for record in rdr.records() {
@@ -0,0 +1,34 @@
+use plotters::{chart::ChartContext, coord::{cartesian::Cartesian2d, types::RangedCoordf64}, element::{EmptyElement, Rectangle}, style::{Color, ShapeStyle}};
+use plotters_svg::SVGBackend;
+
+use crate::backend::setup::board::Board;
+
+use super::board_parameters::UIBoardParameters;
+
+// knights are squares on their node that grow with the level. Active knights are filled
+pub fn draw(
+    chart: &mut ChartContext<'_, SVGBackend<'_>, Cartesian2d<RangedCoordf64, RangedCoordf64>>, 
+    ui_parameters: &UIBoardParameters, 
+    points: &Vec<(f64, f64)>,
+    board: &Board,
+) {
+    let fig_scale = ui_parameters.size;
+    let v_player_colors = &ui_parameters.v_player_colors;
+    let knight_scale = ui_parameters.knight_scale;
+
+    for knight in &board.knights {
+        let point = &points[knight.node as usize];
+        let player_color = v_player_colors[knight.player as usize];
+        let half_width = (fig_scale * (knight_scale * knight.level) as f64) as i32;
+
+        let style = if knight.active {
+            ShapeStyle::from(&player_color).filled()
+        } else {
+            player_color.stroke_width((fig_scale * 3.0) as u32)
+        };
+
+        chart.draw_series(std::iter::once(
+            EmptyElement::at(*point) + Rectangle::new([(-half_width, -half_width), (half_width, half_width)], style)
+        )).expect("Error drawing knights");
+    }
+}
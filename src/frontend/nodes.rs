@@ -4,7 +4,7 @@ use plotters::{chart::{ChartBuilder, ChartContext}, coord::{cartesian::Cartesian
 use plotters_svg::SVGBackend;
 use regex::Regex;
 
-use crate::backend::setup::{game::Game, node::Node, shape::get_n_node_rings};
+use crate::backend::{io::assets::get_drawing_path, setup::{game::Game, node::Node, shape::get_n_node_rings}};
 
 use super::{board_parameters::UIBoardParameters, coords::{add_cart_coords_nodes, add_cart_coords_tiles, add_conc_coords_nodes}};
 
@@ -49,7 +49,10 @@ pub fn create_svg(
             }
         }
 
-    let root = SVGBackend::new("data/assets/nodes.svg", ((fig_scale * x_dim as f64) as u32, (fig_scale * y_dim as f64) as u32)).into_drawing_area();
+    ui_parameters.register_fonts();
+
+    let svg_path = get_drawing_path("nodes.svg");
+    let root = SVGBackend::new(&svg_path, ((fig_scale * x_dim as f64) as u32, (fig_scale * y_dim as f64) as u32)).into_drawing_area();
         root.fill(&ui_parameters.bg_color).expect("Error filling the drawing area");
 
     // Create a chart
@@ -69,7 +72,7 @@ pub fn create_svg(
 
 
 pub fn modify_svg() -> Result<(), Box<dyn std::error::Error>> {
-    let svg_data = fs::read_to_string("data/assets/nodes.svg")?;
+    let svg_data = fs::read_to_string(&get_drawing_path("nodes.svg"))?;
 
     // Define the regex pattern to match polygon elements
     let re_polygon = Regex::new(r"(<circle[^>]*>)").unwrap();
@@ -106,7 +109,7 @@ pub fn modify_svg() -> Result<(), Box<dyn std::error::Error>> {
     });
 
     // Save the modified SVG data to a file
-    let mut file = File::create("data/assets/nodes.svg").expect("Unable to create file");
+    let mut file = File::create(&get_drawing_path("nodes.svg")).expect("Unable to create file");
     file.write_all(modified_svg_data.as_bytes()).expect("Unable to write data");
 
     Ok(())
@@ -0,0 +1,36 @@
+use plotters::{chart::ChartContext, coord::{cartesian::Cartesian2d, types::RangedCoordf64}, element::Circle, series::LineSeries, style::{Color, ShapeStyle}};
+use plotters_svg::SVGBackend;
+
+use crate::backend::setup::board::Board;
+
+use super::board_parameters::UIBoardParameters;
+
+// ships are thinner than roads and carry a hull in the middle of the edge
+pub fn draw(
+    chart: &mut ChartContext<'_, SVGBackend<'_>, Cartesian2d<RangedCoordf64, RangedCoordf64>>, 
+    ui_parameters: &UIBoardParameters, 
+    points: &Vec<(f64, f64)>,
+    board: &Board,
+) {
+    let fig_scale = ui_parameters.size;
+    let v_player_colors = &ui_parameters.v_player_colors;
+    let ship_width = ui_parameters.ship_width;
+    let ship_scale = ui_parameters.ship_scale;
+
+    for ship in &board.ships {
+        let start = &points[ship.nodes.0 as usize];
+        let end = &points[ship.nodes.1 as usize];
+        let player_color = v_player_colors[ship.player as usize];
+
+        chart.draw_series(
+            LineSeries::new(
+                vec![(start.0, start.1), (end.0, end.1)],
+                player_color.stroke_width((fig_scale * ship_width as f64) as u32),
+            )
+        ).expect("Error drawing ships");
+
+        chart.draw_series(std::iter::once(
+            Circle::new(((start.0 + end.0) / 2.0, (start.1 + end.1) / 2.0), (fig_scale * ship_scale as f64) as u32, ShapeStyle::from(&player_color).filled())
+        )).expect("Error drawing ships");
+    }
+}
@@ -4,7 +4,7 @@ use plotters::{chart::{ChartBuilder, ChartContext}, coord::{cartesian::Cartesian
 use plotters_svg::SVGBackend;
 use regex::Regex;
 
-use crate::backend::setup::{game::Game, node::Node, shape::get_n_node_rings, tile::Tile};
+use crate::backend::{io::assets::get_drawing_path, setup::{game::Game, node::Node, shape::get_n_node_rings, tile::Tile}};
 
 use super::{board_parameters::UIBoardParameters, coords::{add_cart_coords_nodes, add_cart_coords_tiles, add_conc_coords_nodes}};
 
@@ -35,7 +35,10 @@ pub fn create_svg(
     let mut tiles = board.tiles.clone();
     tiles = add_cart_coords_tiles(tiles, n_tile_rings, &tile_shape);
 
-    let root = SVGBackend::new("data/assets/tiles.svg", ((fig_scale * x_dim as f64) as u32, (fig_scale * y_dim as f64) as u32)).into_drawing_area();
+    ui_parameters.register_fonts();
+
+    let svg_path = get_drawing_path("tiles.svg");
+    let root = SVGBackend::new(&svg_path, ((fig_scale * x_dim as f64) as u32, (fig_scale * y_dim as f64) as u32)).into_drawing_area();
         root.fill(&ui_parameters.bg_color).expect("Error filling the drawing area");
 
     // Create a chart
@@ -55,7 +58,7 @@ pub fn create_svg(
 
 
 pub fn modify_svg() -> Result<(), Box<dyn std::error::Error>> {
-    let svg_data = fs::read_to_string("data/assets/tiles.svg")?;
+    let svg_data = fs::read_to_string(&get_drawing_path("tiles.svg"))?;
 
     // Define the regex pattern to match polygon elements
     let re_polygon = Regex::new(r"(<polygon[^>]*>)").unwrap();
@@ -90,7 +93,7 @@ pub fn modify_svg() -> Result<(), Box<dyn std::error::Error>> {
     });
 
     // Save the modified SVG data to a file
-    let mut file = File::create("data/assets/tiles.svg").expect("Unable to create file");
+    let mut file = File::create(&get_drawing_path("tiles.svg")).expect("Unable to create file");
     file.write_all(modified_svg_data.as_bytes()).expect("Unable to write data");
 
     Ok(())
@@ -11,6 +11,16 @@ mod tests {
 
     use super::*;
 
+    // a simple NN player with an untrained network, as the trained model is not part of the repository
+    fn untrained_simple_nn(id: u32) -> backend::setup::player::Player {
+        use ai::simple_nn::evaluate::{SimpleNN, INPUT_SIZE};
+        use backend::setup::player::{Player, PlayerType};
+        use tch::{nn, Device};
+
+        let vs = nn::VarStore::new(Device::Cpu);
+        Player::from_type(id, PlayerType::SimpleNN(SimpleNN::new(&vs.root(), INPUT_SIZE, 1)))
+    }
+
     
     #[test]
     #[should_panic(expected = "Cannot place a road on top of another road.")]
@@ -59,4 +69,1176 @@ mod tests {
         assert_eq!(beginner_game.round.board.scores, vec![4,2,2,2]);
         assert_eq!(beginner_game.round.board.longest_roads, Some(vec![6,1,1,1]));
     }
+
+    #[test]
+    fn test_broken_road() {
+        let beginner_game = Game::from_template_settled("test_broken_road".to_string()).unwrap();
+        // the settlement of player 1 on node 35 cuts the road of player 0
+        assert_eq!(beginner_game.round.board.scores, vec![2,3,2,2]);
+        assert_eq!(beginner_game.round.board.longest_roads, Some(vec![3,1,1,1]));
+    }
+
+    #[test]
+    fn test_tied_road() {
+        let beginner_game = Game::from_template_settled("test_tied_road".to_string()).unwrap();
+        assert_eq!(beginner_game.round.board.scores, vec![2,2,2,2]);
+        assert_eq!(beginner_game.round.board.longest_roads, Some(vec![5,5,1,1]));
+        assert_eq!(beginner_game.round.board.prev_longest_road, None);
+    }
+
+    #[test]
+    fn test_longest_road_ships() {
+        use backend::{round::{action::Action, longest_road::get_longest_road, phase::Phase}, setup::ship::Ship};
+
+        // player 0 has a road of 5 from its settlement on node 6 to node 36
+        let mut beginner_game = Game::from_template_settled("test_longest_road".to_string()).unwrap();
+        let roads = beginner_game.round.board.roads.clone().unwrap();
+        let nodes = beginner_game.round.board.nodes.clone();
+        let free_neighbor = |i_node: u32| -> u32 {
+            beginner_game.parameters.node_adjacency[i_node as usize].iter().flatten().copied()
+                .find(|&i_next| !roads.iter().any(|road| road.nodes.0 == i_next || road.nodes.1 == i_next))
+                .unwrap()
+        };
+
+        // ships continue the road at the player's settlement
+        let ship_at_settlement = Ship { player: 0, nodes: (6, free_neighbor(6)), turn: 0 };
+        assert_eq!(get_longest_road(0, &roads, &vec![ship_at_settlement.clone()], &nodes, &vec![]), 6);
+
+        // but not at the open end of the road
+        let ship_at_road_end = Ship { player: 0, nodes: (36, free_neighbor(36)), turn: 0 };
+        assert_eq!(get_longest_road(0, &roads, &vec![ship_at_road_end], &nodes, &vec![]), 5);
+
+        // the board counts the ships of every player
+        beginner_game.round.board.ships.push(ship_at_settlement);
+        beginner_game.round.phase = Phase::Building;
+        beginner_game.round.active_player = 0;
+        beginner_game.take_action(Action::NoBuying, None);
+        assert_eq!(beginner_game.round.board.longest_roads, Some(vec![6, 1, 1, 1]));
+    }
+
+    #[test]
+    fn test_longest_road_transfer() {
+        use backend::round::{action::Action, phase::Phase};
+
+        let mut beginner_game = Game::from_template_settled("test_longest_road".to_string()).unwrap();
+        beginner_game.round.board.budgets[1] = vec![10; 5];
+
+        for (i_road, (start_node, end_node)) in [(15, 27), (27, 40), (40, 39), (39, 26), (26, 38)].iter().enumerate() {
+            beginner_game.round.phase = Phase::Building;
+            beginner_game.round.active_player = 1;
+
+            beginner_game.take_action(Action::BuildRoad(*start_node, *end_node), None);
+
+            assert_eq!(beginner_game.round.board.longest_roads.as_ref().unwrap()[1], 2 + i_road as u32);
+
+            // a tie does not take the longest road from its holder
+            if i_road < 4 {
+                assert_eq!(beginner_game.round.board.prev_longest_road, Some((0, 5)));
+                assert_eq!(beginner_game.round.board.scores, vec![4,2,2,2]);
+            }
+        }
+
+        assert_eq!(beginner_game.round.board.prev_longest_road, Some((1, 6)));
+        assert_eq!(beginner_game.round.board.scores, vec![2,4,2,2]);
+    }
+
+    #[test]
+    fn test_dev_card_timing() {
+        use backend::{round::{action::Action, cards::{CardType, KNIGHT_CARD}, phase::Phase}, setup::game_parameters::GameParameters};
+
+        // a deck of a single monopoly card
+        let mut parameters = GameParameters::default().default_settled(None, "test_largest_army".to_string());
+        parameters.init_undrawn_dev_cards = vec![0, 0, 0, 0, 1];
+
+        let mut beginner_game = Game::new(parameters).unwrap();
+        beginner_game.round.board.budgets[1] = vec![10; 5];
+        beginner_game.round.board.drawn_dev_cards[1][0] += 1;
+
+        beginner_game.round.phase = Phase::Building;
+        beginner_game.round.active_player = 1;
+        beginner_game.round.throwing_player = 1;
+        beginner_game.take_action(Action::BuyDevCard, None);
+        assert_eq!(beginner_game.round.board.drawn_dev_cards[1][4], 1);
+        assert_eq!(beginner_game.round.board.get_n_undrawn_dev_cards(), 0);
+        assert!(!beginner_game.get_legal_actions().contains(&Action::BuyDevCard));
+        assert_eq!(beginner_game.round.board.dev_card_turns[1][4], vec![beginner_game.round.turn]);
+
+        // a card bought in this turn cannot be played, and victory cards are never played
+        beginner_game.round.phase = Phase::SecondCardPhase;
+        let legal_actions = beginner_game.get_legal_actions();
+        assert!(!legal_actions.contains(&Action::CardPlay(CardType::MonopolyCard(0))));
+        assert!(!legal_actions.contains(&Action::CardPlay(CardType::VPCard)));
+
+        beginner_game.round.turn += 1;
+        assert!(beginner_game.get_legal_actions().contains(&Action::CardPlay(CardType::MonopolyCard(0))));
+
+        assert!(beginner_game.round.board.public_scores[1] < beginner_game.round.board.scores[1]);
+        beginner_game.reveal_victory_cards();
+        assert_eq!(beginner_game.round.board.public_scores, beginner_game.round.board.scores);
+
+        // a knight played after building ends the turn
+        beginner_game.round.board.drawn_dev_cards[1][KNIGHT_CARD] += 1;
+        let turn = beginner_game.round.turn;
+        let knight_play = beginner_game.get_legal_actions().into_iter()
+                            .find(|action| matches!(action, Action::CardPlay(CardType::KnightCard(_, _, _))))
+                            .unwrap();
+        beginner_game.take_action(knight_play, None);
+        assert_eq!(beginner_game.round.turn, turn + 1);
+        assert_eq!(beginner_game.round.throwing_player, 2);
+    }
+
+    #[test]
+    fn test_resource_cards() {
+        use backend::round::{action::Action, cards::CardType, phase::Phase};
+
+        let mut beginner_game = Game::from_template_settled("test_largest_army".to_string()).unwrap();
+        beginner_game.round.board.drawn_dev_cards[1][3] += 1;
+        beginner_game.round.board.drawn_dev_cards[1][4] += 1;
+        beginner_game.round.phase = Phase::FirstCardPhase;
+        beginner_game.round.active_player = 1;
+
+        let count_cards = |legal_actions: &Vec<Action>| -> (usize, usize) {
+            let n_plenty = legal_actions.iter().filter(|action| matches!(action, Action::CardPlay(CardType::PlentyCard(_, _)))).count();
+            let n_monopoly = legal_actions.iter().filter(|action| matches!(action, Action::CardPlay(CardType::MonopolyCard(_)))).count();
+            (n_plenty, n_monopoly)
+        };
+
+        // every resource can be picked, and each pair of resources appears once
+        let legal_actions = beginner_game.get_legal_actions();
+        assert_eq!(count_cards(&legal_actions), (15, 5));
+        assert!(legal_actions.contains(&Action::CardPlay(CardType::PlentyCard(3, 4))));
+        assert!(!legal_actions.contains(&Action::CardPlay(CardType::PlentyCard(4, 3))));
+        assert!(legal_actions.contains(&Action::CardPlay(CardType::MonopolyCard(4))));
+
+        // with a single brick left in the bank, two bricks cannot be drawn
+        beginner_game.round.board.budgets[0][0] += beginner_game.round.board.bank_stock[0] - 1;
+        beginner_game.round.board.bank_stock[0] = 1;
+
+        let legal_actions = beginner_game.get_legal_actions();
+        assert_eq!(count_cards(&legal_actions), (14, 5));
+        assert!(!legal_actions.contains(&Action::CardPlay(CardType::PlentyCard(0, 0))));
+        assert!(legal_actions.contains(&Action::CardPlay(CardType::PlentyCard(0, 1))));
+    }
+
+    #[test]
+    fn test_bank_stock() {
+        use backend::round::{action::Action, bank::apply_shortage_rule, phase::Phase};
+
+        // nobody receives a resource the bank cannot pay to everyone, unless only one player is owed it
+        assert_eq!(apply_shortage_rule(&vec![vec![1, 1], vec![2, 0]], &vec![2, 5]), vec![vec![0, 1], vec![0, 0]]);
+        assert_eq!(apply_shortage_rule(&vec![vec![3, 1], vec![0, 0]], &vec![2, 5]), vec![vec![2, 1], vec![0, 0]]);
+
+        let mut beginner_game = Game::from_template_settled("test_largest_army".to_string()).unwrap();
+        assert_eq!(beginner_game.round.board.bank_stock, vec![17, 15, 15, 18, 18]);
+
+        // the bank cannot hand out wool it does not hold
+        beginner_game.round.board.budgets[1] = vec![4, 0, 0, 0, 0];
+        beginner_game.round.board.bank_stock[4] = 0;
+        beginner_game.round.phase = Phase::TradingQuote;
+        beginner_game.round.active_player = 1;
+
+        let legal_actions = beginner_game.get_legal_actions();
+        assert!(legal_actions.contains(&Action::BankTrade(0, 3)));
+        assert!(!legal_actions.contains(&Action::BankTrade(0, 4)));
+
+        beginner_game.take_action(Action::BankTrade(0, 3), None);
+        assert_eq!(beginner_game.round.board.bank_stock, vec![17, 17, 15, 18, 18]);
+    }
+
+    #[test]
+    fn test_piece_limits() {
+        use backend::{io::encode_log::LogEncoding, round::{action::Action, cards::{CardType, ROADS_CARD}, phase::Phase, pieces::get_remaining_pieces}};
+
+        let mut beginner_game = Game::from_template_settled("test_longest_road".to_string()).unwrap();
+        beginner_game.parameters.max_pieces = vec![6, 2, 1];
+        beginner_game.round.board.budgets[0] = vec![10; 5];
+        beginner_game.round.phase = Phase::Building;
+        beginner_game.round.active_player = 0;
+
+        // player 0 has placed all roads and settlements
+        let legal_actions = beginner_game.get_legal_actions();
+        assert_eq!(get_remaining_pieces(0, &beginner_game.round.board, &beginner_game.parameters.max_pieces), vec![0, 0, 1]);
+        assert!(!legal_actions.iter().any(|action| matches!(action, Action::BuildRoad(_, _) | Action::BuildSettlement(_))));
+        assert!(legal_actions.contains(&Action::BuildCity(6)));
+
+        // the upgraded settlement returns to the supply
+        beginner_game.take_action(Action::BuildCity(6), None);
+        beginner_game.round.phase = Phase::Building;
+
+        let legal_actions = beginner_game.get_legal_actions();
+        assert_eq!(get_remaining_pieces(0, &beginner_game.round.board, &beginner_game.parameters.max_pieces), vec![0, 1, 0]);
+        assert!(legal_actions.contains(&Action::BuildSettlement(35)));
+        assert!(!legal_actions.iter().any(|action| matches!(action, Action::BuildCity(_))));
+
+        // the extended log encoding holds the remaining roads, settlements, cities and ships of every player in one block
+        beginner_game.parameters.log_encoding = LogEncoding::Extended;
+        let max_pieces = beginner_game.parameters.max_pieces.clone();
+        let max_ships = beginner_game.parameters.max_ships;
+        beginner_game.parameters.max_pieces = vec![20; 3];
+        beginner_game.parameters.max_ships = 20;
+        let row = beginner_game.hot_encode_log(beginner_game.log.last().unwrap());
+        beginner_game.parameters.max_pieces = vec![21; 3];
+        beginner_game.parameters.max_ships = 21;
+        let row_more_pieces = beginner_game.hot_encode_log(beginner_game.log.last().unwrap());
+
+        let i_changed: Vec<usize> = (0..row.len()).filter(|&i| row[i] != row_more_pieces[i]).collect();
+        assert_eq!(row.len(), row_more_pieces.len());
+        assert_eq!(i_changed.len(), 4 * 4);
+        assert_eq!(i_changed[i_changed.len() - 1] - i_changed[0], 4 * 4 - 1);
+        assert!(i_changed.iter().all(|&i| row_more_pieces[i] == row[i] + 1));
+        beginner_game.parameters.max_pieces = max_pieces;
+        beginner_game.parameters.max_ships = max_ships;
+
+        // with one road left, road building places a single free road
+        beginner_game.parameters.max_pieces[0] = 7;
+        beginner_game.round.board.drawn_dev_cards[0][ROADS_CARD] += 1;
+        beginner_game.round.phase = Phase::FirstCardPhase;
+
+        let road_plays: Vec<Action> = beginner_game.get_legal_actions().into_iter()
+                                        .filter(|action| matches!(action, Action::CardPlay(CardType::RoadsCard(_, _, _, _))))
+                                        .collect();
+        assert!(!road_plays.is_empty());
+        assert!(road_plays.iter().all(|action| matches!(action, Action::CardPlay(CardType::RoadsCard(f1, s1, f2, s2)) if (f1, s1) == (f2, s2))));
+
+        beginner_game.take_action(road_plays[0].clone(), None);
+        assert_eq!(get_remaining_pieces(0, &beginner_game.round.board, &beginner_game.parameters.max_pieces)[0], 0);
+    }
+
+    #[test]
+    fn test_setup_payout() {
+        use backend::{round::action::Action, setup::{node_status::NodeStatus, setup_payout::SetupPayout}};
+
+        for (setup_payout, setup_city_round) in [(SetupPayout::Nothing, None), (SetupPayout::SecondRound, None), (SetupPayout::AllRounds, Some(1))] {
+            let mut beginner_game = Game::from_template("beginner-map".to_string()).unwrap();
+            beginner_game.parameters.setup_payout = setup_payout.clone();
+            beginner_game.parameters.setup_city_round = setup_city_round;
+
+            // no production from the dice thrown after the setup
+            for tile in beginner_game.round.board.tiles.iter_mut() {
+                tile.rng = None;
+            }
+
+            let mut expected_budgets = vec![vec![0; 5]; 4];
+
+            for i_setup in 0..8 {
+                let active_player = beginner_game.round.active_player as usize;
+                let action = beginner_game.get_legal_actions()[0].clone();
+
+                if let Action::SetUpMove(settlement_node, _) = action {
+                    if setup_payout.pays_out(i_setup / 4) {
+                        for tile in &beginner_game.round.board.tiles {
+                            match tile.resource {
+                                Some(i_resource) if tile.nodes.contains(&settlement_node) && i_resource < 5 => expected_budgets[active_player][i_resource as usize] += 1,
+                                _ => (),
+                            }
+                        }
+                    }
+
+                    beginner_game.take_action(action, None);
+
+                    let expected_status = if (i_setup >= 4) && setup_city_round.is_some() { NodeStatus::Citied(active_player as u32) } else { NodeStatus::Settled(active_player as u32) };
+                    assert_eq!(beginner_game.round.board.nodes[settlement_node as usize].node_status, expected_status);
+                }
+            }
+
+            assert_eq!(beginner_game.round.board.budgets, expected_budgets);
+            assert!(setup_payout == SetupPayout::Nothing || beginner_game.round.board.budgets.iter().flatten().sum::<u32>() > 0);
+        }
+    }
+
+    #[test]
+    fn test_robber_variants() {
+        use backend::round::{action::Action, phase::Phase};
+
+        let mut beginner_game = Game::from_template_settled("test_largest_army".to_string()).unwrap();
+        let i_desert = beginner_game.round.board.tile_rng.iter().position(|rng| rng.is_none()).unwrap() as u32;
+
+        // hand limit
+        beginner_game.round.board.budgets[1] = vec![2, 2, 2, 2, 1];
+        beginner_game.round.phase = Phase::RobberDiscard;
+        beginner_game.round.active_player = 1;
+        assert!(beginner_game.get_legal_actions().iter().all(|action| matches!(action, Action::DiscardCards(_))));
+
+        beginner_game.parameters.hand_limit = 9;
+        assert_eq!(beginner_game.get_legal_actions(), vec![Action::NoDiscard]);
+
+        // friendly robber (everyone but player 0 has two victory points)
+        beginner_game.round.phase = Phase::RobberMove;
+        beginner_game.round.active_player = 0;
+        assert!(beginner_game.get_legal_actions().iter().any(|action| matches!(action, Action::Robber(_, _, Some(_)))));
+
+        beginner_game.parameters.friendly_robber = Some(2);
+        assert!(!beginner_game.get_legal_actions().iter().any(|action| matches!(action, Action::Robber(_, _, Some(_)))));
+
+        // robber on the desert
+        beginner_game.round.active_player = 1;
+        beginner_game.take_action(Action::Robber(0, 5, None), None);
+        beginner_game.round.phase = Phase::RobberMove;
+        assert!(!beginner_game.get_legal_actions().iter().any(|action| matches!(action, Action::Robber(_, i_tile, _) if *i_tile == i_desert)));
+
+        beginner_game.parameters.robber_on_desert = true;
+        assert!(beginner_game.get_legal_actions().contains(&Action::Robber(0, i_desert, None)));
+
+        // the robber returns to the desert when player 1 throws the dice again
+        beginner_game.parameters.robber_returns_to_desert = true;
+        beginner_game.round.throwing_player = 3;
+        beginner_game.take_action(Action::FinishRound, None);
+        assert_eq!(beginner_game.round.board.v_robbers, vec![5]);
+
+        beginner_game.take_action(Action::FinishRound, None);
+        assert_eq!(beginner_game.round.board.v_robbers, vec![i_desert]);
+        assert_eq!(beginner_game.round.board.tiles[5].rng, beginner_game.round.board.tile_rng[5]);
+    }
+
+    #[test]
+    fn test_blockers() {
+        use ai::simple_nn::encode::encode_action;
+        use backend::{io::encode_log::LogEncoding, round::{action::Action, phase::Phase}, setup::{edge::make_edge, game_parameters::GameParameters}};
+
+        let mut beginner_game = Game::from_template_settled("test_largest_army".to_string()).unwrap();
+
+        // two robbers on the same tile: the tile only produces again once both have left
+        beginner_game.round.board.v_robbers = vec![5, 5];
+        beginner_game.round.board.robber_players = vec![None; 2];
+        beginner_game.round.board.tiles[5].rng = None;
+
+        beginner_game.take_action(Action::Robber(0, 6, None), None);
+        assert_eq!(beginner_game.round.board.tiles[5].rng, None);
+        assert_eq!(beginner_game.round.board.tiles[6].rng, None);
+
+        beginner_game.take_action(Action::Robber(1, 7, None), None);
+        assert_eq!(beginner_game.round.board.tiles[5].rng, beginner_game.round.board.tile_rng[5]);
+        assert_eq!(beginner_game.round.board.v_robbers, vec![6, 7]);
+
+        // a pirate on the edge of a harbor closes it (player 0 settles on the 3:1 harbor between nodes 36 and 37)
+        beginner_game.round.active_player = 0;
+        beginner_game.round.board.budgets[0] = vec![3; 5];
+        beginner_game.take_action(Action::BuildSettlement(36), None);
+        beginner_game.round.board.budgets[0] = vec![3; 5];
+        let i_harbor_edge = beginner_game.parameters.edge_map[&make_edge(36, 37)] as u32;
+
+        beginner_game.round.phase = Phase::TradingQuote;
+        assert!(beginner_game.get_legal_actions().iter().any(|action| matches!(action, Action::HarborTrade(_, _, _))));
+
+        beginner_game.parameters.init_v_pirate = Some(vec![i_harbor_edge]);
+        beginner_game.round.board.v_pirates = vec![i_harbor_edge];
+        assert!(!beginner_game.get_legal_actions().iter().any(|action| matches!(action, Action::HarborTrade(_, _, _))));
+
+        // the pirate moves along the coast, independently of the robbers
+        beginner_game.round.phase = Phase::RobberMove;
+        let legal_actions = beginner_game.get_legal_actions();
+        assert!(legal_actions.iter().any(|action| matches!(action, Action::Robber(_, _, _))));
+        assert!(!legal_actions.iter().any(|action| matches!(action, Action::Pirate(_, i_edge, _) if *i_edge == i_harbor_edge)));
+
+        // pirate moves to different edges are encoded differently
+        let pirate_moves: Vec<Action> = legal_actions.iter().filter(|action| matches!(action, Action::Pirate(_, _, None))).cloned().collect();
+        assert_eq!(beginner_game.get_log_encoding(), LogEncoding::Extended);
+        assert_ne!(encode_action(&beginner_game, &pirate_moves[0]), encode_action(&beginner_game, &pirate_moves[1]));
+
+        beginner_game.take_action(pirate_moves[0].clone(), None);
+        assert_eq!(beginner_game.round.board.v_robbers, vec![6, 7]);
+        assert_eq!(beginner_game.round.phase, Phase::FirstCardPhase);
+
+        beginner_game.round.phase = Phase::TradingQuote;
+        beginner_game.round.active_player = 0;
+        assert!(beginner_game.get_legal_actions().iter().any(|action| matches!(action, Action::HarborTrade(_, _, _))));
+
+        // the simple NN player does not fit the encoding of games with a pirate
+        let mut nn_parameters = GameParameters::default();
+        nn_parameters.init_v_pirate = Some(vec![i_harbor_edge]);
+        nn_parameters.v_players[0] = untrained_simple_nn(0);
+        assert!(Game::new(nn_parameters).is_err());
+    }
+
+    #[test]
+    fn test_seafarers() {
+        use ai::simple_nn::encode::encode_action;
+        use backend::{io::encode_log::LogEncoding, round::{action::Action, phase::Phase}, setup::{game_parameters::GameParameters, node_status::NodeStatus, terrain::Terrain}};
+
+        let mut sea_game = Game::from_template("seafarers-islands".to_string()).unwrap();
+
+        // a main island and three small islands
+        assert_eq!(sea_game.parameters.get_islands().iter().flatten().max(), Some(&3));
+
+        // settlements and roads stay on land
+        assert!(sea_game.get_legal_actions().iter().all(|action| match action {
+            Action::SetUpMove(node, end) => sea_game.parameters.is_land_node(*node) && sea_game.parameters.is_land_edge(*node, *end),
+            _ => true,
+        }));
+
+        // player 0 settles on the coast of the main island
+        let coast_node = (0..sea_game.round.board.nodes.len() as u32)
+                            .find(|&i_node| {
+                                let terrains: Vec<Terrain> = sea_game.parameters.node_tiles_adjacency[i_node as usize].iter().map(|&i_tile| sea_game.parameters.get_terrain(i_tile)).collect();
+                                terrains.len() == 3 && terrains.contains(&Terrain::Sea) && terrains.iter().any(|terrain| matches!(terrain, Terrain::Land(_)))
+                            })
+                            .unwrap();
+        let land_neighbor = sea_game.round.board.nodes[coast_node as usize].neighbors.iter().flatten().find(|&&n| sea_game.parameters.is_land_edge(coast_node, n)).unwrap().clone();
+        sea_game.take_action(Action::SetUpMove(coast_node, land_neighbor), None);
+        assert_eq!(sea_game.round.board.node_islands[coast_node as usize], Some(0));
+        assert_eq!(sea_game.round.board.home_islands, vec![0]);
+
+        // ships sail from the settlement, roads do not go to sea
+        sea_game.round.phase = Phase::Building;
+        sea_game.round.active_player = 0;
+        sea_game.round.board.budgets[0] = vec![5; 5];
+        let legal_actions = sea_game.get_legal_actions();
+        assert!(legal_actions.iter().all(|action| match action {
+            Action::BuildRoad(start, end) => sea_game.parameters.is_land_edge(*start, *end),
+            Action::BuildShip(start, end) => sea_game.parameters.is_sea_edge(*start, *end),
+            _ => true,
+        }));
+
+        // ships are encoded with their own action code and edge
+        let ship_builds: Vec<Action> = legal_actions.iter().filter(|action| matches!(action, Action::BuildShip(_, _))).cloned().collect();
+        assert_eq!(sea_game.get_log_encoding(), LogEncoding::Extended);
+        assert_ne!(encode_action(&sea_game, &ship_builds[0]), encode_action(&sea_game, &ship_builds[1]));
+
+        let build_ship = ship_builds.iter().find(|action| matches!(action, Action::BuildShip(start, end) if *start == coast_node || *end == coast_node)).unwrap().clone();
+        sea_game.take_action(build_ship, None);
+        assert_eq!(sea_game.round.board.ships.len(), 1);
+        assert_eq!(sea_game.round.board.budgets[0], vec![5, 5, 4, 5, 4]);
+
+        // ships cannot be moved in the turn they were built
+        sea_game.round.phase = Phase::Building;
+        assert!(!sea_game.get_legal_actions().iter().any(|action| matches!(action, Action::MoveShip(_, _, _, _))));
+        sea_game.round.turn += 1;
+        assert!(sea_game.get_legal_actions().iter().any(|action| matches!(action, Action::MoveShip(_, _, _, _))));
+
+        // the first settlement on another island earns the island bonus
+        let island_node = sea_game.parameters.tile_nodes[31].iter()
+                            .find(|&&i_node| sea_game.round.board.nodes[i_node as usize].node_status == NodeStatus::Free && !sea_game.parameters.tile_nodes[32].contains(&i_node))
+                            .unwrap().clone();
+        sea_game.take_action(Action::BuildSettlement(island_node), None);
+        assert_eq!(sea_game.round.board.discovered_islands[0], vec![3]);
+        assert_eq!(sea_game.round.board.scores[0], 2 + sea_game.parameters.island_bonus);
+
+        // a settlement on the gold field is paid a resource of choice
+        let gold_node = sea_game.parameters.tile_nodes[20].iter().find(|&&i_node| sea_game.round.board.nodes[i_node as usize].node_status == NodeStatus::Free).unwrap().clone();
+        sea_game.round.active_player = 1;
+        sea_game.round.board.budgets[1] = vec![1; 5];
+        sea_game.take_action(Action::BuildSettlement(gold_node), None);
+
+        while sea_game.round.phase != Phase::GoldChoice {
+            sea_game.round.phase = Phase::SecondCardPhase;
+            sea_game.round.throwing_player = 0;
+            sea_game.round.board.budgets = vec![vec![0; 5]; 4];
+            sea_game.take_action(Action::FinishRound, None);
+        }
+
+        assert_eq!(sea_game.round.active_player, 1);
+        assert!(sea_game.get_legal_actions().iter().all(|action| matches!(action, Action::ChooseGold(v_chosen) if v_chosen.iter().sum::<u32>() == 1)));
+
+        sea_game.take_action(Action::ChooseGold(vec![0, 0, 0, 1, 0]), None);
+        assert_eq!(sea_game.round.board.budgets[1][3], 1);
+        assert_eq!(sea_game.round.phase, Phase::FirstCardPhase);
+        assert_eq!(sea_game.round.active_player, 1);
+
+        // the simple NN player does not fit the encoding of games with sea and gold fields
+        let mut nn_parameters = GameParameters::default().default_from_template(None, "seafarers-islands".to_string());
+        nn_parameters.v_players[0] = untrained_simple_nn(0);
+        assert!(Game::new(nn_parameters).is_err());
+    }
+
+    #[test]
+    fn test_cities_knights() {
+        use ai::simple_nn::encode::encode_action;
+        use backend::{io::encode_log::LogEncoding, round::{action::Action, cities_knights::{get_commodity_trade_rate, ProgressCard, BARBARIAN_DISTANCE, RESOURCE_MONOPOLY, WARLORD}, longest_road::get_longest_road, phase::Phase}, setup::{game_parameters::GameParameters, knight::Knight, node_status::NodeStatus, ruleset::Ruleset}};
+
+        let mut ck_game = Game::new(GameParameters::default().with_ruleset(Ruleset::CitiesAndKnights)).unwrap();
+        assert_eq!(ck_game.parameters.n_winning_vp, 13);
+        assert_eq!(ck_game.get_log_encoding(), LogEncoding::Extended);
+
+        // the extended log encoding holds the moves of the variants, the remaining pieces and ships of every player and the Cities & Knights state
+        let base_game = Game::new(GameParameters::default()).unwrap();
+        let base_width = base_game.hot_encode_log(&base_game.log[0]).len();
+        let n_edges = ck_game.parameters.edge_map.len();
+        let n_nodes = ck_game.round.board.nodes.len();
+
+        let n_phases_actions = 2 + 10;
+        let n_action_fields = 5 * n_edges + n_nodes + 2 * 3 + 8 + 5;
+        let n_cities_knights = 3 + 8 + 4 * (3 + 4) + n_nodes * (4 + 2) + 3 * 4 + 1 + 3;
+        assert_eq!(ck_game.hot_encode_log(&ck_game.log[0]).len(), base_width + n_phases_actions + n_action_fields + 4 * 4 + 4 * n_edges + n_cities_knights);
+
+        // science, trade and politics decks
+        assert_eq!(ck_game.round.board.progress_deck, vec![7, 6, 3]);
+        assert_eq!(ck_game.round.board.get_n_undrawn_dev_cards(), 0);
+
+        // player 0 places a city next to a forest in the second setup round
+        let i_forest = ck_game.round.board.tiles.iter().position(|tile| tile.resource == Some(2) && tile.rng.is_some()).unwrap();
+        let setup_move = ck_game.get_legal_actions().into_iter()
+                            .find(|action| matches!(action, Action::SetUpMove(node, _) if ck_game.parameters.tile_nodes[i_forest].contains(node)))
+                            .unwrap();
+        let (city_node, road_end) = match setup_move {
+            Action::SetUpMove(node, end) => (node, end),
+            _ => unreachable!(),
+        };
+        ck_game.round.phase_count = 2 * ck_game.parameters.n_players - 1;
+        ck_game.take_action(setup_move, None);
+        assert_eq!(ck_game.round.board.nodes[city_node as usize].node_status, NodeStatus::Citied(0));
+
+        // the city produces one lumber and one paper
+        while ck_game.round.board.commodities[0][0] == 0 {
+            ck_game.round.phase = Phase::SecondCardPhase;
+            ck_game.round.throwing_player = 0;
+            ck_game.round.board.barbarian_position = 0;
+            ck_game.round.board.budgets = vec![vec![0; 5]; 4];
+            ck_game.take_action(Action::FinishRound, None);
+        }
+        assert_eq!(ck_game.round.board.commodities[0], vec![1, 0, 0]);
+        assert_eq!(ck_game.round.board.budgets[0][2], 1);
+
+        // paper pays for the first science improvement, wool and ore for a knight along the road, grain activates it
+        ck_game.round.phase = Phase::Building;
+        ck_game.round.active_player = 0;
+        ck_game.round.board.budgets[0] = vec![0, 1, 0, 1, 1];
+        let legal_actions = ck_game.get_legal_actions();
+        assert!(legal_actions.contains(&Action::BuildImprovement(0)));
+        assert!(legal_actions.contains(&Action::BuildKnight(road_end)));
+
+        ck_game.take_action(Action::BuildImprovement(0), None);
+        assert_eq!(ck_game.round.board.improvements[0], vec![1, 0, 0]);
+        assert_eq!(ck_game.round.board.commodities[0], vec![0, 0, 0]);
+
+        ck_game.take_action(Action::BuildKnight(road_end), None);
+        ck_game.take_action(Action::ActivateKnight(road_end), None);
+        assert!(ck_game.round.board.knights[0].active);
+        assert_eq!(ck_game.round.board.budgets[0], vec![0; 5]);
+
+        // the active knight fends off the barbarians on their arrival: player 0 becomes a defender of Catan
+        ck_game.round.board.barbarian_position = BARBARIAN_DISTANCE - 1;
+        while ck_game.round.board.barbarian_position > 0 {
+            ck_game.round.phase = Phase::SecondCardPhase;
+            ck_game.round.throwing_player = 0;
+            ck_game.round.board.budgets = vec![vec![0; 5]; 4];
+            ck_game.take_action(Action::FinishRound, None);
+        }
+        assert_eq!(ck_game.round.board.defender_points[0], 1);
+        assert!(!ck_game.round.board.knights[0].active);
+        assert_eq!(ck_game.round.board.nodes[city_node as usize].node_status, NodeStatus::Citied(0));
+
+        // progress cards are played in the building phase and go back under their deck
+        ck_game.round.phase = Phase::Building;
+        ck_game.round.active_player = 0;
+        ck_game.round.board.progress_cards[0][WARLORD] = 1;
+        ck_game.round.board.progress_cards[0][RESOURCE_MONOPOLY] = 1;
+        ck_game.round.board.budgets = vec![vec![0; 5], vec![0, 3, 0, 0, 0], vec![0, 1, 0, 0, 0], vec![0; 5]];
+        ck_game.round.board.public_budgets = ck_game.round.board.budgets.clone();
+        let n_politics_cards = ck_game.round.board.progress_deck[2];
+
+        let legal_actions = ck_game.get_legal_actions();
+        assert!(legal_actions.contains(&Action::PlayProgressCard(ProgressCard::Warlord)));
+        assert_ne!(encode_action(&ck_game, &Action::PlayProgressCard(ProgressCard::ResourceMonopoly(1))), encode_action(&ck_game, &Action::PlayProgressCard(ProgressCard::ResourceMonopoly(3))));
+
+        // the warlord activates the knight, the resource monopoly takes up to two cards from every opponent
+        ck_game.take_action(Action::PlayProgressCard(ProgressCard::Warlord), None);
+        assert!(ck_game.round.board.knights[0].active);
+        assert_eq!(ck_game.round.board.progress_deck[2], n_politics_cards + 1);
+
+        ck_game.take_action(Action::PlayProgressCard(ProgressCard::ResourceMonopoly(1)), None);
+        assert_eq!(ck_game.round.board.budgets[0][1], 3);
+        assert_eq!(ck_game.round.board.budgets[1][1], 1);
+        assert_eq!(ck_game.round.board.budgets[2][1], 0);
+        assert_eq!(ck_game.round.board.progress_cards[0][WARLORD] + ck_game.round.board.progress_cards[0][RESOURCE_MONOPOLY], 0);
+
+        // commodities count towards the hand limit and are discarded with the resources
+        ck_game.round.phase = Phase::RobberDiscard;
+        ck_game.round.active_player = 0;
+        ck_game.round.discard_queue = vec![0];
+        ck_game.round.board.budgets = vec![vec![2, 2, 2, 1, 0], vec![0; 5], vec![0; 5], vec![0; 5]];
+        ck_game.round.board.public_budgets = ck_game.round.board.budgets.clone();
+        ck_game.round.board.commodities[0] = vec![1, 0, 0];
+
+        let discard = Action::DiscardCards(vec![2, 1, 0, 0, 0, 1, 0, 0]);
+        assert!(ck_game.get_legal_actions().contains(&discard));
+        ck_game.take_action(discard, None);
+        assert_eq!(ck_game.round.board.budgets[0], vec![0, 1, 2, 1, 0]);
+        assert_eq!(ck_game.round.board.commodities[0], vec![0, 0, 0]);
+
+        // commodities trade with the bank at 4:1, 3:1 with a generic harbor
+        ck_game.round.phase = Phase::TradingQuote;
+        ck_game.round.active_player = 0;
+        ck_game.round.board.commodities[0] = vec![0, 4, 0];
+        let rate = get_commodity_trade_rate(&ck_game, 0);
+        assert!(ck_game.get_legal_actions().contains(&Action::CommodityTrade(1, 3)));
+        ck_game.take_action(Action::CommodityTrade(1, 3), None);
+        assert_eq!(ck_game.round.board.commodities[0], vec![0, 4 - rate, 0]);
+        assert_eq!(ck_game.round.board.budgets[0][3], 2);
+
+        // the base game knows no knights
+        let mut beginner_game = Game::from_template_settled("test_largest_army".to_string()).unwrap();
+        beginner_game.round.phase = Phase::Building;
+        beginner_game.round.board.budgets = vec![vec![5; 5]; 4];
+        assert!(!beginner_game.get_legal_actions().iter().any(|action| matches!(action, Action::BuildKnight(_) | Action::BuildImprovement(_))));
+
+        // an opponent's knight cuts a road like a settlement
+        let road_game = Game::from_template_settled("test_longest_road".to_string()).unwrap();
+        let roads = road_game.round.board.roads.clone().unwrap();
+        let middle_node = roads.iter().filter(|road| road.player == 0).map(|road| road.nodes.1)
+                            .find(|&i_node| roads.iter().filter(|road| road.player == 0 && (road.nodes.0 == i_node || road.nodes.1 == i_node)).count() == 2)
+                            .unwrap();
+        let knight = Knight { player: 1, node: middle_node, level: 1, active: false };
+        assert_eq!(get_longest_road(0, &roads, &vec![], &road_game.round.board.nodes, &vec![]), 5);
+        assert!(get_longest_road(0, &roads, &vec![], &road_game.round.board.nodes, &vec![knight]) < 5);
+
+        // the simple NN player does not fit the encoding of Cities & Knights games
+        let mut nn_parameters = GameParameters::default().with_ruleset(Ruleset::CitiesAndKnights);
+        nn_parameters.v_players[0] = untrained_simple_nn(0);
+        assert!(Game::new(nn_parameters).is_err());
+    }
+
+    #[test]
+    fn test_layout() {
+        use backend::setup::{game_parameters::GameParameters, shape::check_layout};
+
+        // a hexagon of radius 2 in axial coordinates has the topology of the standard board
+        let hexagon: Vec<(i32, i32)> = (-2..=2_i32).flat_map(|q| (-2..=2_i32).map(move |r| (q, r))).filter(|(q, r)| (q + r).abs() <= 2).collect();
+        let parameters = GameParameters::default().with_layout(hexagon);
+        assert_eq!(parameters.n_tile_rings, 3);
+        assert_eq!(parameters.node_adjacency.len(), 54);
+        assert_eq!(parameters.edge_map.len(), 72);
+        assert_eq!(parameters.get_coast_edges().len(), 30);
+
+        // a rectangle of 5 x 4 tiles
+        let rectangle_game = Game::from_template("rectangle".to_string()).unwrap();
+        let parameters = &rectangle_game.parameters;
+        assert_eq!(rectangle_game.round.board.tiles.len(), 20);
+        assert_eq!(rectangle_game.round.board.nodes.len(), 58);
+        assert_eq!(parameters.get_coast_edges().len(), 34);
+
+        for (i_node, neighbors) in parameters.node_adjacency.iter().enumerate() {
+            for neighbor in neighbors.iter().flatten() {
+                assert!(parameters.node_adjacency[*neighbor as usize].contains(&Some(i_node as u32)));
+            }
+            assert!((1..=3).contains(&parameters.node_tiles_adjacency[i_node].len()));
+        }
+        assert!(!rectangle_game.get_legal_actions().is_empty());
+
+        assert!(check_layout(&vec![(0, 0, None), (0, 0, None)], 2).is_err());
+        assert!(check_layout(&vec![(0, 0, Some(1))], 1).is_err());
+    }
+
+    #[test]
+    fn test_event_deck() {
+        use backend::{round::{action::Action, phase::Phase}, setup::{dice_source::{DiceMode, DiceSource}, event_deck::EventDeck, game_parameters::GameParameters, rng_service::RngService}};
+
+        let mut rng = RngService::new(&GameParameters::default());
+
+        // without a reshuffle point, 36 cards yield the distribution of two dice exactly
+        let mut deck = EventDeck::new(2, 6, 0);
+        let mut sums = vec![0; 13];
+        for _ in 0..36 {
+            deck = deck.throw(&mut rng);
+            assert_eq!(deck.faces.len(), 2);
+            sums[deck.draw.unwrap() as usize] += 1;
+        }
+        assert_eq!(sums, vec![0, 0, 1, 2, 3, 4, 5, 6, 5, 4, 3, 2, 1]);
+        assert_eq!(deck.n_reshuffles, 1);
+
+        // the deck is reshuffled once only five cards are left
+        let mut deck = EventDeck::new(2, 6, 5);
+        for _ in 0..32 {
+            deck = deck.throw(&mut rng);
+        }
+        assert_eq!(deck.n_reshuffles, 2);
+
+        // the source of randomness is chosen per game
+        let parameters = GameParameters { dice_mode: DiceMode::EventDeck, ..GameParameters::default() };
+        let mut deck_game = Game::new(parameters).unwrap();
+        assert!(matches!(deck_game.round.board.dice, DiceSource::EventDeck(_)));
+
+        deck_game.round.phase = Phase::SecondCardPhase;
+        deck_game.take_action(Action::FinishRound, None);
+        assert_eq!(deck_game.round.board.dice_outcome, Some(deck_game.round.board.dice.faces().iter().sum()));
+    }
+
+    #[test]
+    fn test_rng_streams() {
+        use backend::setup::{game_parameters::GameParameters, rng_service::{RngService, Stream}};
+        use rand::Rng;
+
+        // the same master seed replays the same game, AI choices included
+        let play_game = |master_seed: u64| {
+            let mut game = Game::new(GameParameters::default().with_master_seed(master_seed)).unwrap();
+            let mut history = vec![];
+
+            for _ in 0..300 {
+                let legal_actions = game.get_legal_actions();
+                let player_function = game.parameters.v_players[game.round.active_player as usize].player_function.unwrap();
+                let action = player_function(&game, legal_actions).unwrap();
+
+                history.push((action.clone(), game.round.board.dice_outcome));
+                game.take_action(action, None);
+            }
+
+            (history, game)
+        };
+
+        let (history, game) = play_game(17);
+        let (replayed_history, replayed_game) = play_game(17);
+        assert_eq!(history, replayed_history);
+        assert_eq!(game.round.board.rng.get_n_draws(Stream::Dice), replayed_game.round.board.rng.get_n_draws(Stream::Dice));
+
+        let (other_history, _) = play_game(18);
+        assert_ne!(history, other_history);
+
+        // drawing from one stream leaves the others untouched
+        let mut rng = game.round.board.rng.clone();
+        let n_dice_draws = rng.get_n_draws(Stream::Dice);
+        rng.rng(Stream::DevDeck);
+        rng.rng(Stream::Seat(2));
+        assert_eq!(rng.get_n_draws(Stream::Dice), n_dice_draws);
+        assert_eq!(rng.get_n_draws(Stream::DevDeck), game.round.board.rng.get_n_draws(Stream::DevDeck) + 1);
+        assert_ne!(rng.get_seed(Stream::Seat(0)), rng.get_seed(Stream::Seat(1)));
+
+        // a stream seeded one higher does not replay the draws of its neighbour shifted by one
+        let parameters = GameParameters::default();
+        let next_parameters = GameParameters { dice_seed: parameters.dice_seed + 1, ..parameters.clone() };
+        let mut rng = RngService::new(&parameters);
+        let mut next_rng = RngService::new(&next_parameters);
+        rng.rng(Stream::Dice);
+        assert_ne!(rng.rng(Stream::Dice).gen::<u64>(), next_rng.rng(Stream::Dice).gen::<u64>());
+
+        // the games of a series share the map, but no other seed
+        let series_parameters = parameters.clone().with_game_index(1);
+        assert_eq!(series_parameters.tile_seed, parameters.tile_seed);
+        assert_ne!(series_parameters.dice_seed, parameters.dice_seed + 1);
+        assert_ne!(series_parameters.ai_seed, parameters.with_game_index(2).ai_seed);
+    }
+
+    #[test]
+    fn test_dev_deck() {
+        use backend::{round::{action::Action, phase::Phase}, setup::game_parameters::GameParameters};
+
+        // the whole deck is shuffled at setup, identically for the same seed, and drawn from the top
+        let mut game = Game::new(GameParameters::default()).unwrap();
+        let mut replayed_game = Game::new(GameParameters::default()).unwrap();
+        assert_eq!(game.round.board.get_n_undrawn_dev_cards(), 25);
+
+        // a lookahead on a purchase draws from a reshuffled copy of the deck
+        let mut drawn_cards = vec![];
+        let mut lookahead_cards = vec![];
+
+        for i_card in 0..25 {
+            game.round.board.budgets[0] = vec![10; 5];
+            game.round.phase = Phase::Building;
+            game.round.phase_count = 0;
+            let mut lookahead = game.clone_for_lookahead();
+            lookahead.take_action(Action::BuyDevCard, None);
+            lookahead_cards.push((0..5).find(|&i| lookahead.round.board.drawn_dev_cards[0][i] > game.round.board.drawn_dev_cards[0][i]).unwrap());
+
+            let drawn_before = game.round.board.drawn_dev_cards[0].clone();
+            for game in [&mut game, &mut replayed_game] {
+                game.round.board.budgets[0] = vec![10; 5];
+                game.round.phase = Phase::Building;
+                game.round.phase_count = 0;
+                game.take_action(Action::BuyDevCard, None);
+            }
+
+            assert_eq!(game.round.board.get_n_undrawn_dev_cards(), 24 - i_card);
+            assert_eq!(game.round.board.drawn_dev_cards[0], replayed_game.round.board.drawn_dev_cards[0]);
+            drawn_cards.push((0..5).find(|&i| game.round.board.drawn_dev_cards[0][i] > drawn_before[i]).unwrap());
+        }
+
+        assert_ne!(lookahead_cards, drawn_cards);
+
+        assert_eq!(game.round.board.drawn_dev_cards[0], game.parameters.init_undrawn_dev_cards);
+        assert!(!game.get_legal_actions().contains(&Action::BuyDevCard));
+    }
+
+    #[test]
+    fn test_template_costs() {
+        use backend::{io::read_parameters::read_named_matrix_csv, round::{bank::RESOURCE_NAMES, pieces::BUILDING_NAMES}};
+
+        // rows and columns are matched by name
+        let costs_game = Game::from_template("test_costs".to_string()).unwrap();
+        assert_eq!(costs_game.parameters.building_costs[0], vec![2, 0, 2, 0, 0]);
+        assert_eq!(costs_game.parameters.building_costs[2], vec![0, 2, 0, 3, 0]);
+        assert_eq!(costs_game.parameters.building_costs[4], vec![0, 0, 1, 0, 1]);
+        assert_eq!(costs_game.parameters.init_undrawn_dev_cards, vec![0, 20, 0, 0, 0]);
+        assert_eq!(costs_game.round.board.get_n_undrawn_dev_cards(), 20);
+
+        // the default parameters take their prices from the default template
+        let default_game = Game::from_template("default".to_string()).unwrap();
+        assert_eq!(default_game.parameters.building_costs, Game::new(Default::default()).unwrap().parameters.building_costs);
+        assert_eq!(default_game.parameters.building_costs[2], vec![0, 2, 0, 3, 0]);
+
+        // every building has to be priced
+        assert!(read_named_matrix_csv("data/templates/test_costs/costs.csv", &BUILDING_NAMES[..4], &RESOURCE_NAMES).is_err());
+        assert!(read_named_matrix_csv("data/templates/test_costs/dev_cards.csv", &BUILDING_NAMES, &["count"]).is_err());
+    }
+
+    #[test]
+    fn test_template_json() {
+        use backend::io::template::Template;
+
+        // a JSON template sets up the same game as the CSV directory it was converted from
+        let json_game = Game::from_template_settled("test_json_template".to_string()).unwrap();
+        let csv_game = Game::from_template_settled("test_longest_road".to_string()).unwrap();
+        assert_eq!(json_game.round.board.scores, csv_game.round.board.scores);
+        assert_eq!(json_game.round.board.longest_roads, csv_game.round.board.longest_roads);
+        assert_eq!(Template::load("test_json_template").unwrap(), Template::import_csv("data/templates/test_longest_road").unwrap());
+
+        // errors name the offending entry and field
+        let tiles: Vec<String> = (0..19).map(|i_tile| format!("{{\"resource\": 0, \"rng\": {}}}", if i_tile == 2 { 7 } else { 6 })).collect();
+        let json = format!("{{\"tiles\": [{}], \"harbors\": []}}", tiles.join(", "));
+        let error = Template::from_json_str(&json).unwrap_err().to_string();
+        assert!(error.starts_with("tiles[2].rng"), "{}", error);
+
+        let json = json.replace("\"harbors\": []", "\"harbors\": [], \"roads\": [{\"player\": 0, \"nodes\": [0, 20]}]");
+        let json = json.replace("\"rng\": 7", "\"rng\": 8");
+        let error = Template::from_json_str(&json).unwrap_err().to_string();
+        assert!(error.starts_with("roads[0].nodes"), "{}", error);
+
+        // unknown fields are rejected
+        assert!(Template::from_json_str("{\"tiles\": [], \"harbors\": [], \"robbers\": 2}").is_err());
+    }
+
+    #[test]
+    fn test_template_registry() {
+        use backend::io::{template::Template, template_registry::{TemplateRegistry, TemplateSource}};
+
+        // the built-in templates match their directories
+        let embedded = TemplateRegistry::new(vec![TemplateSource::Embedded]);
+        assert_eq!(embedded.resolve("default").unwrap(), Template::import_csv("data/templates/default").unwrap());
+        assert_eq!(embedded.resolve("beginner-map").unwrap(), Template::import_csv("data/templates/beginner-map").unwrap());
+        assert!(embedded.resolve("test_longest_road").is_err());
+
+        // a user directory shadows the built-in template of the same name
+        let user_dir = std::env::temp_dir().join(format!("catan-templates-{}", std::process::id()));
+        std::fs::create_dir_all(&user_dir).unwrap();
+
+        let mut template = embedded.resolve("default").unwrap();
+        template.description = Some("Default board without harbors".to_string());
+        template.harbors = vec![];
+        template.write_json(user_dir.join("default.json").to_str().unwrap()).unwrap();
+
+        let registry = TemplateRegistry::new(vec![TemplateSource::Dir(user_dir.clone()), TemplateSource::Embedded]);
+        assert!(registry.resolve("default").unwrap().harbors.is_empty());
+
+        let infos = registry.list();
+        let default_info = infos.iter().find(|info| info.name == "default").unwrap();
+        assert_eq!(default_info.source, TemplateSource::Dir(user_dir.clone()));
+        assert_eq!(default_info.description, Some("Default board without harbors".to_string()));
+        assert_eq!(infos.iter().filter(|info| info.name == "default").count(), 1);
+        assert_eq!(infos.iter().find(|info| info.name == "rectangle").unwrap().n_tile_rings, None);
+        assert!(infos.iter().find(|info| info.name == "beginner-map").unwrap().settled);
+
+        // explicit paths bypass the search path
+        let empty_registry = TemplateRegistry::new(vec![]);
+        assert!(empty_registry.resolve(user_dir.join("default.json").to_str().unwrap()).unwrap().harbors.is_empty());
+        assert!(empty_registry.resolve("data/templates/rectangle").is_ok());
+        assert!(empty_registry.resolve("default").is_err());
+
+        std::fs::remove_dir_all(&user_dir).unwrap();
+    }
+
+    #[test]
+    fn test_embedded_assets() {
+        use backend::{analysis::BoardAnalysis, io::assets::{read_asset, EMBEDDED_ASSETS}};
+        use frontend::board_parameters::UIBoardParameters;
+
+        // the embedded assets are the files of data/
+        for (path, content) in EMBEDDED_ASSETS.iter() {
+            assert_eq!(content.to_vec(), std::fs::read("data/".to_owned() + path).unwrap());
+            assert_eq!(read_asset(path).unwrap(), content.to_vec());
+        }
+
+        // assets that are not embedded are looked up in data/ and otherwise missing
+        assert!(read_asset("templates/test_json_template.json").is_ok());
+        assert!(read_asset("ai/missing/weights.csv").is_err());
+
+        let ui_parameters = UIBoardParameters::default();
+        assert_eq!(ui_parameters.v_player_colors.len(), 6);
+        assert_eq!(ui_parameters.v_resource_names.len(), ui_parameters.v_resource_colors.len());
+
+        // drawing needs no installed fonts
+        let game = Game::from_template("default".to_string()).unwrap();
+        let analysis = BoardAnalysis::new(&game.round.board, &game.parameters);
+        let file_path = std::env::temp_dir().join(format!("catan-heatmap-{}.svg", std::process::id()));
+
+        game.draw_heatmap(ui_parameters, &analysis, file_path.to_string_lossy().to_string()).unwrap();
+        assert!(std::fs::read_to_string(&file_path).unwrap().contains("Dela Gothic One"));
+
+        std::fs::remove_file(&file_path).unwrap();
+    }
+
+    #[test]
+    fn test_outside_checkout() {
+        use std::{env, fs, process::Command};
+        use backend::setup::{game_parameters::GameParameters, player::{Player, PlayerType}};
+        use frontend::board_parameters::UIBoardParameters;
+
+        // the test reruns itself in an empty working directory, without override directories
+        if env::var_os("CATAN_TEST_OUTSIDE_CHECKOUT").is_none() {
+            let dir = env::temp_dir().join(format!("catan-outside-checkout-{}", std::process::id()));
+            fs::create_dir_all(&dir).unwrap();
+
+            let output = Command::new(env::current_exe().unwrap())
+                            .args(["--exact", "tests::test_outside_checkout"])
+                            .env("CATAN_TEST_OUTSIDE_CHECKOUT", "1")
+                            .env_remove("CATAN_ASSET_DIR")
+                            .env_remove("CATAN_TEMPLATE_PATH")
+                            .current_dir(&dir)
+                            .output()
+                            .unwrap();
+
+            fs::remove_dir_all(&dir).unwrap();
+            assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stdout));
+            return
+        }
+
+        let mut game = Game::new(GameParameters::default()).unwrap();
+        assert!(Game::from_template_settled("beginner-map".to_string()).is_ok());
+        assert_eq!(UIBoardParameters::default().v_player_colors.len(), 6);
+
+        // the classic players start from the embedded weights
+        assert!(matches!(Player::new_classic(0).player_type, PlayerType::Classic(weights) if weights == vec![vec![0.0; 13]; 5]));
+        assert!(matches!(Player::new_classic_plus(0).player_type, PlayerType::ClassicPlus(weights) if weights == vec![vec![0.0; 13]; 5]));
+
+        // the simple NN player cannot be built without its model
+        assert!(Player::new_simple_nn(0).is_err());
+
+        for _ in 0..50 {
+            let legal_actions = game.get_legal_actions();
+            let player_function = game.parameters.v_players[game.round.active_player as usize].player_function.unwrap();
+            let action = player_function(&game, legal_actions).unwrap();
+            game.take_action(action, None);
+        }
+    }
+
+    #[test]
+    fn test_simultaneous_discards() {
+        use backend::round::{action::Action, phase::Phase};
+
+        let mut beginner_game = Game::from_template_settled("test_largest_army".to_string()).unwrap();
+
+        // throw until the robber is activated
+        while beginner_game.round.phase != Phase::RobberDiscard {
+            beginner_game.round.phase = Phase::SecondCardPhase;
+            beginner_game.round.throwing_player = 1;
+            beginner_game.round.board.budgets = vec![vec![2, 2, 2, 2, 2], vec![0, 1, 0, 0, 0], vec![0; 5], vec![3, 3, 2, 0, 0]];
+            beginner_game.round.board.public_budgets = beginner_game.round.board.budgets.clone();
+            beginner_game.take_action(Action::FinishRound, None);
+        }
+
+        // only players 3 and 0 are over the hand limit, starting after the thrower
+        assert_eq!(beginner_game.round.discard_queue, vec![3, 0]);
+        assert_eq!(beginner_game.round.active_player, 3);
+
+        beginner_game.take_action(Action::DiscardCards(vec![2, 2, 0, 0, 0]), None);
+        assert_eq!(beginner_game.round.active_player, 0);
+        // the first discard stays hidden until everyone has discarded
+        assert_eq!(beginner_game.round.board.public_budgets[3], vec![3, 3, 2, 0, 0]);
+        assert_eq!(beginner_game.round.action, Some(Action::DiscardCards(vec![0; 5])));
+        let i_first_discard = beginner_game.log.len() - 1;
+        assert_eq!(beginner_game.log[i_first_discard].round.as_ref().unwrap().action, Some(Action::DiscardCards(vec![0; 5])));
+
+        beginner_game.take_action(Action::DiscardCards(vec![1, 1, 1, 1, 1]), None);
+        // once applied, the first discard is revealed in the log
+        assert_eq!(beginner_game.log[i_first_discard].round.as_ref().unwrap().action, Some(Action::DiscardCards(vec![2, 2, 0, 0, 0])));
+        assert_eq!(beginner_game.round.board.budgets[0], vec![1; 5]);
+        assert_eq!(beginner_game.round.board.budgets[3], vec![1, 1, 2, 0, 0]);
+        assert_eq!(beginner_game.round.phase, Phase::RobberMove);
+        assert_eq!(beginner_game.round.active_player, 2);
+    }
+
+    #[test]
+    fn test_league_schedule() {
+        let schedule = backend::league::get_round_robin_schedule(5, 4);
+        assert_eq!(schedule.len(), 20);
+
+        // every entrant sits in every seat equally often
+        for i_entrant in 0..5 {
+            for seat in 0..4 {
+                assert_eq!(schedule.iter().filter(|seating| seating[seat] == i_entrant).count(), 4);
+            }
+        }
+
+        let new_elos = backend::league::update_elo(&vec![1500.0; 4], &vec![10, 7, 5, 5], 16.0);
+        assert!((new_elos.iter().sum::<f64>() - 6000.0).abs() < 1e-9);
+        assert!(new_elos[0] > new_elos[1] && new_elos[1] > new_elos[2]);
+        assert_eq!(new_elos[2], new_elos[3]);
+
+        // the ratings are persisted in the output directory, wherever the working directory is
+        use backend::{league::{League, LeagueEntrant}, setup::player::PlayerType};
+
+        let output_dir = std::env::temp_dir().join(format!("catan-league-{}", std::process::id()));
+        let entrants = vec![LeagueEntrant { name: "random".to_string(), player_type: PlayerType::Random }];
+
+        let mut league = League::new("test".to_string(), entrants.clone(), output_dir.clone());
+        league.ratings[0].elo = 1600.0;
+        league.save_ratings().unwrap();
+        assert!(output_dir.join("test.csv").is_file());
+
+        let mut reloaded_league = League::new("test".to_string(), entrants, output_dir.clone());
+        reloaded_league.load_ratings().unwrap();
+        assert_eq!(reloaded_league.ratings[0].elo, 1600.0);
+
+        std::fs::remove_dir_all(&output_dir).unwrap();
+    }
+
+    #[test]
+    fn test_tournament_seatings() {
+        use backend::tournament::{get_seatings, SeatingDesign};
+
+        assert_eq!(get_seatings(4, &SeatingDesign::AllPermutations).len(), 24);
+
+        let latin_square = get_seatings(4, &SeatingDesign::LatinSquare);
+        assert_eq!(latin_square.len(), 4);
+
+        for seat in 0..4 {
+            let mut column: Vec<usize> = latin_square.iter().map(|seating| seating[seat]).collect();
+            column.sort();
+            assert_eq!(column, vec![0,1,2,3]);
+        }
+    }
+
+    #[test]
+    fn test_random_map() {
+        use backend::setup::{game_parameters::GameParameters, map_generator::{get_tile_neighbors, MapConstraints}};
+
+        let constraints = MapConstraints::default();
+
+        let mut parameters = GameParameters::default();
+        parameters.tile_seed = 7;
+
+        assert_eq!(parameters.get_coast_edges().len(), 30);
+
+        let map = parameters.clone().with_random_map(&constraints).unwrap();
+        let same_map = parameters.with_random_map(&constraints).unwrap();
+        assert_eq!(map.v_tile_resources, same_map.v_tile_resources);
+        assert_eq!(map.init_tile_rng, same_map.init_tile_rng);
+
+        // the desert carries no number token
+        for (resource, rng) in map.v_tile_resources.iter().zip(map.init_tile_rng.iter()) {
+            assert_eq!(resource.is_none(), rng.is_none());
+        }
+
+        let tile_neighbors = get_tile_neighbors(&map.tile_nodes);
+        for (i_tile, neighbors) in tile_neighbors.iter().enumerate() {
+            if map.init_tile_rng[i_tile] == Some(6) || map.init_tile_rng[i_tile] == Some(8) {
+                for &j_tile in neighbors {
+                    assert!(map.init_tile_rng[j_tile] != Some(6) && map.init_tile_rng[j_tile] != Some(8));
+                }
+            }
+        }
+
+        assert_eq!(map.init_harbors.as_ref().unwrap().len(), 9);
+
+        let game = Game::new(map).unwrap();
+        assert_eq!(game.round.board.tiles[game.round.board.v_robbers[0] as usize].resource, None);
+    }
+
+    #[test]
+    fn test_board_analysis() {
+        use backend::analysis::BoardAnalysis;
+
+        let beginner_game = Game::from_template("beginner-map".to_string()).unwrap();
+        let analysis = BoardAnalysis::new(&beginner_game.round.board, &beginner_game.parameters);
+
+        // 18 number tokens with 58 pips in total
+        assert_eq!(analysis.resource_pips.iter().sum::<u32>(), 58);
+
+        // every node belongs to three tiles at most, and three tiles carry 15 pips at most
+        for node_value in &analysis.node_values {
+            assert!(node_value.production <= 15.0 / 36.0 + 1e-9);
+        }
+
+        let best = analysis.best_spots[0] as usize;
+        assert!(analysis.node_values.iter().all(|value| value.production <= analysis.node_values[best].production));
+
+        assert_eq!(analysis.draft_picks[0][0], analysis.best_spots[0]);
+        assert!(analysis.draft_picks.iter().all(|picks| picks.len() == 2));
+        assert_eq!(analysis.harbor_access.len(), 9);
+    }
+
+    #[test]
+    fn test_six_players() {
+        use backend::{io::encode_log::LogEncoding, round::{action::Action, phase::Phase}, setup::game_parameters::GameParameters};
+
+        let mut six_player_game = Game::from_template("default-6-players".to_string()).unwrap();
+        assert_eq!(six_player_game.parameters.n_tile_rings, 4);
+        assert_eq!(six_player_game.parameters.n_players, 6);
+        assert_eq!(six_player_game.parameters.v_players.len(), 6);
+        assert_eq!(six_player_game.round.board.tiles.len(), 37);
+        assert_eq!(six_player_game.round.board.nodes.len(), 96);
+        assert_eq!(six_player_game.parameters.init_undrawn_dev_cards.iter().sum::<u32>(), 34);
+        assert_eq!(six_player_game.parameters.resource_supply, vec![24; 5]);
+
+        // the simple NN player only fits the base encoding of the default board
+        let mut nn_parameters = GameParameters::default().with_n_players(6);
+        nn_parameters.v_players[0] = untrained_simple_nn(0);
+        assert!(Game::new(nn_parameters).is_err());
+
+        let mut nn_parameters = GameParameters::default();
+        nn_parameters.v_players[0] = untrained_simple_nn(0);
+        assert!(Game::new(nn_parameters).is_ok());
+
+        // after the second card phase, every other player gets a special building phase
+        six_player_game.round.phase = Phase::SecondCardPhase;
+        six_player_game.round.throwing_player = 2;
+        six_player_game.round.active_player = 2;
+        six_player_game.take_action(Action::NoCardPlay, None);
+        assert_eq!(six_player_game.round.phase, Phase::SpecialBuilding);
+        assert_eq!(six_player_game.round.active_player, 3);
+
+        for i_player in [4, 5, 0, 1] {
+            six_player_game.take_action(Action::NoBuying, None);
+            assert_eq!(six_player_game.round.phase, Phase::SpecialBuilding);
+            assert_eq!(six_player_game.round.active_player, i_player);
+        }
+
+        six_player_game.take_action(Action::NoBuying, None);
+        assert_ne!(six_player_game.round.phase, Phase::SpecialBuilding);
+
+        // the log of the last special building move has a field for the special building phase, with the code of the round logs
+        assert_eq!(six_player_game.get_log_encoding(), LogEncoding::Extended);
+        let row = six_player_game.hot_encode_log(six_player_game.log.last().unwrap());
+        let i_phase = 7 + 3 * 6 + 11;
+        assert_eq!(row[i_phase..i_phase + 11], [0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0]);
+        assert_eq!(six_player_game.round.throwing_player, 3);
+    }
+
+    #[test]
+    fn test_largest_army() {
+        let beginner_game = Game::from_template_settled("test_largest_army".to_string()).unwrap();
+        assert_eq!(beginner_game.round.board.scores, vec![4,2,2,2]);
+        assert_eq!(beginner_game.round.board.prev_largest_army, Some((0, 3)));
+    }
+
+    #[test]
+    fn test_largest_army_tie() {
+        let beginner_game = Game::from_template_settled("test_largest_army_tie".to_string()).unwrap();
+        assert_eq!(beginner_game.round.board.scores, vec![2,2,2,2]);
+        assert_eq!(beginner_game.round.board.prev_largest_army, None);
+    }
+
+    #[test]
+    fn test_largest_army_transfer() {
+        use backend::round::{action::Action, cards::CardType, phase::Phase};
+
+        let mut beginner_game = Game::from_template_settled("test_largest_army".to_string()).unwrap();
+        beginner_game.round.board.drawn_dev_cards[1][1] += 3;
+
+        for (i_knight, i_tile) in [5, 6, 7].iter().enumerate() {
+            beginner_game.round.phase = Phase::FirstCardPhase;
+            beginner_game.round.active_player = 1;
+            beginner_game.round.throwing_player = 1;
+            beginner_game.round.cards_played = 0;
+
+            beginner_game.take_action(Action::CardPlay(CardType::KnightCard(0, *i_tile, None)), None);
+
+            assert_eq!(beginner_game.round.board.armies[1], 2 + i_knight as u32);
+
+            // a tie does not take the largest army from its holder
+            if i_knight < 2 {
+                assert_eq!(beginner_game.round.board.prev_largest_army, Some((0, 3)));
+                assert_eq!(beginner_game.round.board.scores, vec![4,2,2,2]);
+            }
+        }
+
+        assert_eq!(beginner_game.round.board.prev_largest_army, Some((1, 4)));
+        assert_eq!(beginner_game.round.board.scores, vec![2,4,2,2]);
+    }
 }
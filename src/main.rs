@@ -1,5 +1,6 @@
 use std::error::Error;
 
+use catan_cli::backend::io::template_registry::{TemplateRegistry, TemplateSource};
 use catan_cli::backend::monte_carlo::simulate_games;
 use catan_cli::backend::setup::game::Game;
 
@@ -11,7 +12,16 @@ use catan_cli::frontend::board_parameters::UIBoardParameters;
 fn main() -> Result<(), Box<dyn Error>> {
 
     let n_sims = 1200;
-    let template = "beginner-map".to_string();
+
+    // catan-cli list: show the templates on the search path. catan-cli <template>: play a template (by name or path)
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    if args.first().map(|arg| arg.as_str()) == Some("list") {
+        print_templates(&TemplateRegistry::default());
+        return Ok(())
+    }
+
+    let template = args.first().cloned().unwrap_or("beginner-map".to_string());
 
     let mut beginner_game = Game::from_template(template.clone())?;
     let mut game_parameters = beginner_game.parameters.clone();
@@ -20,7 +30,7 @@ fn main() -> Result<(), Box<dyn Error>> {
         game_parameters.v_players[i] = Player::new_classic(i as u32);
     }
 
-    // game_parameters.v_players[0] = Player::new_simple_nn(0); 
+    // game_parameters.v_players[0] = Player::new_simple_nn(0).unwrap(); 
 
     game_parameters.v_players[0].player_type = PlayerType::Human; 
 
@@ -40,3 +50,25 @@ fn main() -> Result<(), Box<dyn Error>> {
 
     Ok(())
 }
+
+
+fn print_templates(registry: &TemplateRegistry) {
+    for info in registry.list() {
+        let source = match &info.source {
+            TemplateSource::Dir(dir) => dir.display().to_string(),
+            TemplateSource::Embedded => "built-in".to_string(),
+        };
+
+        let board = match info.n_tile_rings {
+            Some(n_tile_rings) => format!("{} rings", n_tile_rings),
+            None => "layout".to_string(),
+        };
+
+        println!("{:<28} {:>3} tiles ({}), {} players, {} harbors{}  [{}]", info.name, info.n_tiles, board, info.n_players, info.n_harbors,
+                    if info.settled { ", settled" } else { "" }, source);
+
+        if let Some(description) = &info.description {
+            println!("    {}", description);
+        }
+    }
+}